@@ -1,3 +1,5 @@
+use helpers::ocr::recognize_str;
+
 #[derive(Clone)]
 struct DeviceVideoSystemState {
     register_x: isize,
@@ -24,7 +26,7 @@ impl<'a> DeviceVideoSystem<'a> {
         }
     }
 
-    fn run(&'a mut self) -> RunIterator {
+    fn run(&'a mut self) -> RunIterator<'a> {
         RunIterator { device: self }
     }
 
@@ -71,7 +73,7 @@ impl<'a> Iterator for RunIterator<'a> {
             return None;
         }
 
-        return Some(state);
+        Some(state)
     }
 }
 
@@ -83,12 +85,13 @@ pub fn part1(input: &Vec<&str>) -> isize {
         .sum()
 }
 
-pub fn part2(input: &Vec<&str>) -> String {
+/// Pixels drawn by the CRT, `#` for lit ones.
+pub fn crt_image(input: &Vec<&str>) -> String {
     DeviceVideoSystem::init(input)
         .run()
         .map(|state| {
             let mut string = String::new();
-            let pixel_x = ((state.cycle - 1) % 40) as usize;
+            let pixel_x = (state.cycle - 1) % 40;
 
             if state.cycle > 1 && pixel_x == 0 {
                 string += "\n";
@@ -107,6 +110,10 @@ pub fn part2(input: &Vec<&str>) -> String {
         .collect()
 }
 
+pub fn part2(input: &Vec<&str>) -> String {
+    recognize_str(&crt_image(input)).expect("CRT image should spell capital letters")
+}
+
 #[cfg(test)]
 mod tests {
     use helpers::input_lines;
@@ -270,9 +277,9 @@ noop";
     }
 
     #[test]
-    fn test_part2() {
+    fn test_crt_image() {
         assert_eq!(
-            part2(&input()),
+            crt_image(&input()),
             "\
 ##..##..##..##..##..##..##..##..##..##..
 ###...###...###...###...###...###...###.
@@ -282,4 +289,24 @@ noop";
 #######.......#######.......#######....."
         );
     }
+
+    #[test]
+    fn test_decode() {
+        let image = "\
+####.#..#.###..###..####.####..##..#....
+...#.#..#.#..#.#..#.#....#....#..#.#....
+..#..#..#.#..#.#..#.###..###..#....#....
+.#...#..#.###..###..#....#....#....#....
+#....#..#.#....#.#..#....#....#..#.#....
+####..##..#....#..#.#....####..##..####.";
+
+        assert_eq!(recognize_str(image).unwrap(), "ZUPRFECL");
+    }
+
+    #[test]
+    #[should_panic(expected = "CRT image should spell capital letters")]
+    fn test_part2_unreadable() {
+        // The example draws stripes rather than letters
+        part2(&input());
+    }
 }
@@ -1,3 +1,5 @@
+use helpers::math::lcm_all;
+
 trait Operation: OperationClone {
     fn execute(&self, old: usize) -> usize;
 }
@@ -177,10 +179,12 @@ pub fn part1(input: &Vec<&str>) -> usize {
 
 pub fn part2(input: &Vec<&str>) -> usize {
     let mut monkeys = parse_monkeys(input);
-    let common_multiple: usize = monkeys
-        .iter()
-        .map(|monkey| monkey.test.divisible_by)
-        .product();
+    // Reducing modulo the LCM of the divisors keeps every divisibility test unchanged
+    let common_multiple = lcm_all(monkeys.iter().map(|monkey| monkey.test.divisible_by));
+    assert!(
+        common_multiple.checked_mul(common_multiple).is_some(),
+        "Squaring reduced worry levels would overflow"
+    );
 
     calc_monkey_business(&mut monkeys, 10_000, |value| value % common_multiple)
 }
@@ -1,90 +1,57 @@
 extern crate helpers;
 
-use std::collections::HashMap;
-
-use petgraph::algo::astar;
-use petgraph::graph::NodeIndex;
-use petgraph::Graph;
-
+use helpers::search::bfs_multi;
 use helpers::{Coord, Grid};
 
 fn get_elevation(elevation: char) -> u8 {
     match elevation {
         'S' => 0,
-        'E' => 'z' as u8 - 'a' as u8,
-        _ => elevation as u8 - 'a' as u8,
+        'E' => b'z' - b'a',
+        _ => elevation as u8 - b'a',
     }
 }
 
-fn make_graph(input: &Grid<char>) -> (HashMap<Coord, NodeIndex>, Graph<u8, ()>) {
-    let mut graph = Graph::new();
-    let mut nodes = HashMap::new();
-
-    for (coord, elevation) in input.iter() {
-        nodes.insert(coord, graph.add_node(get_elevation(*elevation)));
-    }
-
-    for (coord, node_index) in nodes.iter() {
-        let elevation = graph[*node_index];
+fn climbable_neighbors(input: &Grid<char>, coord: Coord) -> Vec<Coord> {
+    let elevation = get_elevation(*input.get(coord));
 
-        for (coord, neighbour_elevation) in input.neighbors_iter(*coord, false) {
-            if get_elevation(*neighbour_elevation) as isize - elevation as isize <= 1 {
-                graph.add_edge(*node_index, *nodes.get(&coord).unwrap(), ());
-            }
-        }
-    }
-
-    (nodes, graph)
+    input
+        .neighbors_iter(coord, false)
+        .filter(|(_, &neighbour_elevation)| {
+            get_elevation(neighbour_elevation) as isize - elevation as isize <= 1
+        })
+        .map(|(coord, _)| coord)
+        .collect()
 }
 
-fn find_lower_steps(graph: &Graph<u8, ()>, start: NodeIndex, dest: NodeIndex) -> Option<usize> {
-    astar(graph, start, |_dest| dest == _dest, |_| 1, |_| 0).map(|(steps, _)| steps)
+fn find_lower_steps(input: &Grid<char>, starts: Vec<Coord>, dest: Coord) -> Option<usize> {
+    bfs_multi(
+        starts,
+        |&coord| climbable_neighbors(input, coord),
+        |&coord| coord == dest,
+    )
+    .map(|(steps, _)| steps)
 }
 
 pub fn part1(input: &Grid<char>) -> usize {
-    let current_position = input
-        .iter()
-        .find(|(_, &c)| c == 'S')
-        .map(|(position, _)| position)
-        .unwrap();
-    let end_position = input
-        .iter()
-        .find(|(_, &c)| c == 'E')
-        .map(|(position, _)| position)
-        .unwrap();
-
-    let (nodes, graph) = make_graph(input);
+    let markers = input.value_index();
+    let start = markers.get(&'S').expect("No start marker 'S'");
+    let dest = markers.get(&'E').expect("No destination marker 'E'")[0];
 
-    let start = *nodes.get(&current_position).unwrap();
-    let dest = *nodes.get(&end_position).unwrap();
-
-    find_lower_steps(&graph, start, dest).unwrap()
+    find_lower_steps(input, start.clone(), dest).unwrap()
 }
 
 pub fn part2(input: &Grid<char>) -> usize {
-    let lowest_positions = input
-        .iter()
-        .filter(|(_, &c)| c == 'S' || c == 'a')
-        .map(|(position, _)| position)
-        .collect::<Vec<Coord>>();
-    let end_position = input
-        .iter()
-        .find(|(_, &c)| c == 'E')
-        .map(|(position, _)| position)
-        .unwrap();
-
-    let (nodes, graph) = make_graph(input);
-
-    lowest_positions
+    let markers = input.value_index();
+    let start = markers.get(&'S').expect("No start marker 'S'");
+    let dest = markers.get(&'E').expect("No destination marker 'E'")[0];
+    // A map without any other 'a' only has the start at the lowest elevation
+    let lowest_positions = start
         .iter()
-        .filter_map(|position| {
-            let start = *nodes.get(&position).unwrap();
-            let dest = *nodes.get(&end_position).unwrap();
+        .chain(markers.get(&'a').into_iter().flatten())
+        .copied()
+        .collect();
 
-            find_lower_steps(&graph, start, dest)
-        })
-        .min()
-        .unwrap()
+    find_lower_steps(input, lowest_positions, dest).unwrap()
 }
 
 #[cfg(test)]
@@ -93,7 +60,7 @@ mod tests {
 
     use super::*;
 
-    fn input<'a>() -> Grid<char> {
+    fn input() -> Grid<char> {
         let input = "\
 Sabqponm
 abcryxxl
@@ -112,4 +79,11 @@ abdefghi";
     fn test_part2() {
         assert_eq!(part2(&input()), 29)
     }
+
+    #[test]
+    fn test_part2_without_a() {
+        let input = input_grid("SbcdefghijklmnopqrstuvwxyE");
+
+        assert_eq!(part2(&input), 25)
+    }
 }
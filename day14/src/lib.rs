@@ -1,108 +1,107 @@
 use std::collections::HashSet;
 
-#[derive(Clone, PartialEq, Eq, Hash)]
-struct Coord(isize, isize);
+use helpers::lines::{bounds, draw_sparse, polyline, Point};
+use helpers::Grid;
 
-fn parse_input(input: &Vec<&str>) -> (HashSet<Coord>, isize) {
-    let mut grid: HashSet<Coord> = HashSet::new();
-    let mut max_y = 0;
+fn parse_input(input: &Vec<&str>) -> HashSet<Point> {
+    let mut rocks = HashSet::new();
 
     for line in input {
-        let mut from: Option<Coord> = None;
-
-        for part in line.split(" -> ") {
-            let coords = part.split_once(',').unwrap();
-            let to = Coord(coords.0.parse().unwrap(), coords.1.parse().unwrap());
-
-            if let Some(from) = from {
-                let range = if from.0 == to.0 {
-                    from.1.min(to.1)..=from.1.max(to.1)
-                } else {
-                    from.0.min(to.0)..=from.0.max(to.0)
-                };
-
-                for coord in range.map(|i| {
-                    if from.0 == to.0 {
-                        Coord(from.0, i)
-                    } else {
-                        Coord(i, from.1)
-                    }
-                }) {
-                    if coord.1 > max_y {
-                        max_y = coord.1;
-                    }
-                    grid.insert(coord);
-                }
-            }
+        let path = line.split(" -> ").map(|part| {
+            let (x, y) = part.split_once(',').unwrap();
+            (x.parse().unwrap(), y.parse().unwrap())
+        });
 
-            from = Some(to);
-        }
+        draw_sparse(&mut rocks, polyline(path));
     }
 
-    (grid, max_y)
+    rocks
 }
 
-pub fn part1(input: &Vec<&str>) -> usize {
-    let (mut grid, max_y) = parse_input(input);
+struct Cave {
+    grid: Grid<bool>,
+    source: (usize, usize),
+}
+
+impl Cave {
+    /// Grid spanning the rocks and the sand source, `true` cells are blocked.
+    fn new(rocks: &HashSet<Point>) -> Self {
+        let ((min_x, _), (max_x, max_y)) = bounds(rocks.iter().copied().chain([(500, 0)])).unwrap();
+        let size = ((max_x - min_x + 1) as usize, (max_y + 1) as usize);
 
-    let rocks_count = grid.len();
-    let start = Coord(500, 0);
+        let mut grid = Grid::new(size, vec![false; size.0 * size.1]);
+        for rock in rocks {
+            grid.set(((rock.0 - min_x) as usize, rock.1 as usize), true);
+        }
 
-    'generator: loop {
-        let mut sand_position = start.clone();
+        Cave {
+            grid,
+            source: ((500 - min_x) as usize, 0),
+        }
+    }
 
-        'movements: loop {
-            if sand_position.1 >= max_y {
-                break 'generator;
-            }
+    /// Adds a floor two rows below the lowest rock, wide enough for sand to pile up on it.
+    fn add_floor(&mut self) {
+        let floor_y = self.grid.size.1 + 1;
+        let left = floor_y.saturating_sub(self.source.0);
+        let right = (self.source.0 + floor_y + 1).saturating_sub(self.grid.size.0);
+
+        let offset = self.grid.pad(left, 0, right, 1, false);
+        self.source = (self.source.0 + offset.0, self.source.1 + offset.1);
+        self.grid.push_row(vec![true; self.grid.size.0]);
+    }
+
+    /// Drops one unit of sand, returns where it rests or `None` if it falls off the grid.
+    fn drop_sand(&mut self) -> Option<(usize, usize)> {
+        let mut position = self.source;
 
-            for next in &[[0, 1], [-1, 1], [1, 1]] {
-                let next_position = Coord(sand_position.0 + next[0], sand_position.1 + next[1]);
-                if !grid.contains(&next_position) {
-                    sand_position = next_position;
-                    continue 'movements;
+        'movements: loop {
+            for offset in [0, -1, 1] {
+                let next = (position.0 as isize + offset, position.1 as isize + 1);
+
+                match self.grid.maybe_get(next) {
+                    None => return None,
+                    Some(false) => {
+                        position = (next.0 as usize, next.1 as usize);
+                        continue 'movements;
+                    }
+                    Some(true) => {}
                 }
             }
 
-            break;
+            self.grid.set(position, true);
+            return Some(position);
         }
-
-        grid.insert(sand_position);
     }
-
-    grid.len() - rocks_count
 }
 
-pub fn part2(input: &Vec<&str>) -> usize {
-    let (mut grid, max_y) = parse_input(input);
+pub fn part1(input: &Vec<&str>) -> usize {
+    let rocks = parse_input(input);
+    let mut cave = Cave::new(&rocks);
 
-    let rocks_count = grid.len();
-    let start = Coord(500, 0);
-    let floor_y = max_y + 2;
+    let mut sand = 0;
+    while cave.drop_sand().is_some() {
+        sand += 1;
+    }
 
-    loop {
-        if grid.contains(&start) {
-            break;
-        }
+    sand
+}
 
-        let mut sand_position = start.clone();
+pub fn part2(input: &Vec<&str>) -> usize {
+    let rocks = parse_input(input);
+    let mut cave = Cave::new(&rocks);
+    cave.add_floor();
 
-        'movements: loop {
-            for next in &[[0, 1], [-1, 1], [1, 1]] {
-                let next_position = Coord(sand_position.0 + next[0], sand_position.1 + next[1]);
-                if next_position.1 < floor_y && !grid.contains(&next_position) {
-                    sand_position = next_position;
-                    continue 'movements;
-                }
-            }
+    let mut sand = 0;
+    while let Some(position) = cave.drop_sand() {
+        sand += 1;
 
+        if position == cave.source {
             break;
         }
-
-        grid.insert(sand_position);
     }
 
-    grid.len() - rocks_count
+    sand
 }
 
 #[cfg(test)]
@@ -1,5 +1,6 @@
-use std::ops::RangeInclusive;
+use std::collections::HashSet;
 
+use helpers::intervals::IntervalSet;
 use regex::Regex;
 
 struct Coord(isize, isize);
@@ -37,8 +38,8 @@ fn parse_sensors(input: &Vec<&str>) -> Vec<Sensor> {
         .collect()
 }
 
-fn calc_sensors_ranges_for_row(sensors: &Vec<Sensor>, row: isize) -> Vec<RangeInclusive<isize>> {
-    let mut ranges = vec![];
+fn calc_sensors_ranges_for_row(sensors: &Vec<Sensor>, row: isize) -> IntervalSet<isize> {
+    let mut ranges = IntervalSet::new();
 
     for sensor in sensors {
         let distance = sensor.coord.distance(&sensor.beacon);
@@ -50,31 +51,22 @@ fn calc_sensors_ranges_for_row(sensors: &Vec<Sensor>, row: isize) -> Vec<RangeIn
 
         let diff = distance - distance_to_row;
 
-        ranges.push((sensor.coord.0 - diff)..=(sensor.coord.0 + diff));
+        ranges.insert((sensor.coord.0 - diff)..=(sensor.coord.0 + diff));
     }
 
-    ranges.sort_by(|a, b| a.start().cmp(&b.start()));
-
     ranges
 }
 
 pub fn part1(input: &Vec<&str>, row: isize) -> isize {
     let sensors = parse_sensors(input);
     let ranges = calc_sensors_ranges_for_row(&sensors, row);
-    let mut max = ranges[0].end();
-    let mut count = ranges[0].end() - ranges[0].start();
-
-    for range in ranges.iter().skip(1) {
-        if range.start() - max > 1 {
-            count += range.end() - range.start();
-        } else if range.end() > max {
-            count += range.end() - max;
-        }
-
-        max = max.max(range.end());
-    }
+    let beacons: HashSet<isize> = sensors
+        .iter()
+        .filter(|sensor| sensor.beacon.1 == row && ranges.contains(sensor.beacon.0))
+        .map(|sensor| sensor.beacon.0)
+        .collect();
 
-    count
+    ranges.len() - beacons.len() as isize
 }
 
 pub fn part2(input: &Vec<&str>, min: isize, max: isize) -> isize {
@@ -82,15 +74,11 @@ pub fn part2(input: &Vec<&str>, min: isize, max: isize) -> isize {
 
     for y in min..=max {
         let ranges = calc_sensors_ranges_for_row(&sensors, y);
-        let mut max = ranges[0].end();
-
-        for range in ranges.iter().skip(1) {
-            if range.start() - max > 1 {
-                let x = range.start() - 1;
-                return x * 4000000 + y;
-            }
+        let gaps = ranges.gaps_within(min..=max);
+        let gap = gaps.iter().next();
 
-            max = max.max(range.end());
+        if let Some(gap) = gap {
+            return gap.start * 4000000 + y;
         }
     }
 
@@ -1,206 +1,225 @@
-use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::collections::HashMap;
 
+use helpers::interner::Interner;
+use helpers::search::bfs_all;
+use helpers::state_search::{dfs_branch_and_bound, State};
+use helpers::BitSet;
 use regex::Regex;
 
-type Label = String;
+type ValveId = u8;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 struct Valve {
     flow_rate: usize,
-    tunnels: Vec<Label>,
+    tunnels: Vec<ValveId>,
 }
 
-fn parse_valves(input: &Vec<&str>) -> HashMap<Label, Valve> {
+/// Valves indexed by their interned label.
+fn parse_valves(input: &Vec<&str>) -> (Interner<ValveId>, Vec<Valve>) {
     let regex = Regex::new(r"Valve (?P<label>[A-Z]{2}) has flow rate=(?P<flow_rate>\d+); tunnels? leads? to valves? (?P<tunnels>.+)").unwrap();
 
-    let mut valves: HashMap<Label, Valve> = HashMap::new();
+    let mut labels: Interner<ValveId> = Interner::new();
+    let mut valves: Vec<Option<Valve>> = Vec::new();
 
     for line in input {
         let captures = regex.captures(line).unwrap();
 
-        valves.insert(
-            captures["label"].to_string(),
-            Valve {
-                flow_rate: captures["flow_rate"].parse().unwrap(),
-                tunnels: captures["tunnels"]
-                    .split(", ")
-                    .map(|s| s.to_string())
-                    .collect(),
-            },
-        );
+        let id = labels.intern(&captures["label"]) as usize;
+        let tunnels = captures["tunnels"]
+            .split(", ")
+            .map(|label| labels.intern(label))
+            .collect();
+
+        valves.resize(labels.len(), None);
+        valves[id] = Some(Valve {
+            flow_rate: captures["flow_rate"].parse().unwrap(),
+            tunnels,
+        });
     }
-    return valves;
+
+    let valves = valves
+        .into_iter()
+        .enumerate()
+        .map(|(id, valve)| {
+            valve.unwrap_or_else(|| {
+                panic!("Valve {} is never described", labels.resolve(id as ValveId))
+            })
+        })
+        .collect();
+
+    (labels, valves)
 }
 
-struct Item {
-    label: Label,
-    opened: HashSet<Label>,
-    minutes: usize,
-    flow: usize,
+fn find_shortest_paths(start: ValveId, valves: &[Valve]) -> HashMap<ValveId, usize> {
+    let mut distances = bfs_all(start, |&valve| valves[valve as usize].tunnels.clone());
+    distances.remove(&start);
+
+    distances
 }
 
-#[derive(Debug, PartialEq, Eq, Ord)]
-struct Vertex {
-    label: Label,
-    dist: usize,
+/// Valves with their distances, only the ones worth opening matter after parsing.
+struct Network {
+    valves: Vec<Valve>,
+    useful: BitSet<1>,
+    distances: Vec<Vec<usize>>,
+    start: ValveId,
 }
 
-impl Vertex {
-    fn new(label: Label, dist: usize) -> Vertex {
-        Vertex { label, dist }
+impl Network {
+    fn new(input: &Vec<&str>) -> Self {
+        let (labels, valves) = parse_valves(input);
+        assert!(valves.len() <= 64, "Opened valves must fit in a BitSet<1>");
+
+        let useful = (0..valves.len())
+            .filter(|&valve| valves[valve].flow_rate > 0)
+            .collect();
+        let distances = (0..valves.len())
+            .map(|valve| {
+                let paths = find_shortest_paths(valve as ValveId, &valves);
+
+                (0..valves.len())
+                    .map(|to| paths.get(&(to as ValveId)).copied().unwrap_or(usize::MAX))
+                    .collect()
+            })
+            .collect();
+
+        Network {
+            valves,
+            useful,
+            distances,
+            start: labels.get("AA").expect("No valve AA"),
+        }
     }
-}
 
-impl PartialOrd for Vertex {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(other.dist.cmp(&self.dist))
+    /// Minutes left once `to` is reached and opened, if there is time to do so.
+    fn open_at(&self, from: ValveId, to: usize, minutes: usize) -> Option<usize> {
+        minutes.checked_sub(self.distances[from as usize][to].checked_add(1)?)
     }
 }
 
-fn find_shortest_paths(start: Label, valves: &HashMap<Label, Valve>) -> HashMap<Label, Vec<Label>> {
-    let mut dist = HashMap::new();
-
-    let mut prev = HashMap::new();
-
-    let mut queue: BinaryHeap<Vertex> = BinaryHeap::new();
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct Actor {
+    minutes: usize,
+    valve: ValveId,
+}
 
-    dist.insert(start.clone(), 0);
-    queue.push(Vertex::new(start.clone(), 0));
+/// Actors are kept sorted by decreasing time left and the one with the most moves next.
+#[derive(Clone)]
+struct Exploration<'a> {
+    network: &'a Network,
+    actors: [Actor; 2],
+    opened: BitSet<1>,
+    flow: usize,
+}
 
-    for (label, _) in valves.iter() {
-        if *label == start {
-            continue;
-        }
-        dist.insert(label.to_string(), usize::MAX);
-        // prev[v] ← UNDEFINED
-        queue.push(Vertex::new(label.clone(), usize::MAX));
+impl<'a> Exploration<'a> {
+    fn new(network: &'a Network, minutes: [usize; 2]) -> Self {
+        let mut exploration = Exploration {
+            network,
+            actors: minutes.map(|minutes| Actor {
+                minutes,
+                valve: network.start,
+            }),
+            opened: BitSet::new(),
+            flow: 0,
+        };
+        exploration.sort_actors();
+
+        exploration
     }
 
-    while let Some(vertex) = queue.pop() {
-        let valve = valves.get(&vertex.label).unwrap();
-
-        for tunnel in valve.tunnels.iter() {
-            let alt = dist[&vertex.label] + 1;
-
-            if alt < dist[tunnel] {
-                dist.insert(tunnel.to_string(), alt);
-                prev.insert(tunnel.to_string(), vertex.label.clone());
-            }
-        }
+    fn closed(&self) -> impl Iterator<Item = usize> + '_ {
+        self.network
+            .useful
+            .iter()
+            .filter(|&valve| !self.opened.contains(valve))
+    }
 
-        let mut new_queue = BinaryHeap::new();
-        queue.iter().for_each(|vertex| {
-            new_queue.push(Vertex::new(vertex.label.clone(), dist[&vertex.label]))
-        });
-        queue = new_queue;
+    fn sort_actors(&mut self) {
+        self.actors.sort_by(|a, b| b.cmp(a));
     }
+}
 
-    let mut paths = HashMap::new();
+impl State for Exploration<'_> {
+    type Score = usize;
+    type Key = ([Actor; 2], BitSet<1>);
 
-    for (label, _) in valves.iter() {
-        if *label == start {
-            continue;
+    fn successors(&self) -> Vec<Self> {
+        let actor = self.actors[0];
+        if actor.minutes == 0 {
+            return vec![];
         }
 
-        let mut path = vec![label.clone()];
-        let mut current = label.clone();
+        // Stopping early can leave valves to the other actor
+        let mut retired = self.clone();
+        retired.actors[0].minutes = 0;
+        retired.sort_actors();
 
-        loop {
-            let new_current = prev.get(&current).unwrap().clone();
+        let mut successors = vec![retired];
 
-            if new_current == start {
-                break;
-            }
+        for to in self.closed() {
+            if let Some(minutes) = self.network.open_at(actor.valve, to, actor.minutes) {
+                let mut next = self.clone();
+                next.actors[0] = Actor {
+                    minutes,
+                    valve: to as ValveId,
+                };
+                next.opened.insert(to);
+                next.flow += self.network.valves[to].flow_rate * minutes;
+                next.sort_actors();
 
-            path.push(new_current.clone());
-            current = new_current;
+                successors.push(next);
+            }
         }
-        path.reverse();
-        paths.insert(label.clone(), path);
-    }
 
-    paths
-}
-
-pub fn part1(input: &Vec<&str>) -> usize {
-    let valves = parse_valves(input);
-    let mut shortest_paths = HashMap::new();
-
-    for (label, _) in valves.iter() {
-        shortest_paths.insert(label.clone(), find_shortest_paths(label.clone(), &valves));
+        successors
     }
 
-    let mut queue: VecDeque<Item> = VecDeque::new();
-    queue.push_back(Item {
-        label: "AA".to_string(),
-        opened: HashSet::new(),
-        minutes: 0,
-        flow: 0,
-    });
+    fn score(&self) -> usize {
+        self.flow
+    }
 
-    let mut best_flow = 0;
+    // Every closed valve opened by whichever actor could get there first
+    fn upper_bound(&self) -> usize {
+        let network = self.network;
+
+        self.flow
+            + self
+                .closed()
+                .map(|to| {
+                    let minutes = self
+                        .actors
+                        .iter()
+                        .filter_map(|actor| network.open_at(actor.valve, to, actor.minutes))
+                        .max()
+                        .unwrap_or(0);
+
+                    network.valves[to].flow_rate * minutes
+                })
+                .sum::<usize>()
+    }
 
-    while let Some(item) = queue.pop_front() {
-        if item.minutes >= 30 {
-            if item.flow > best_flow {
-                best_flow = item.flow;
-            }
-            continue;
-        }
+    fn key(&self) -> Self::Key {
+        (self.actors, self.opened)
+    }
+}
 
-        let paths = shortest_paths
-            .get(&item.label)
-            .unwrap()
-            .iter()
-            .filter(|(to_label, _)| {
-                let valve = valves.get(*to_label).unwrap();
+pub fn part1(input: &Vec<&str>) -> usize {
+    let network = Network::new(input);
 
-                valve.flow_rate > 0 && !item.opened.contains(*to_label)
-            })
-            .collect::<Vec<_>>();
-
-        if paths.is_empty() {
-            queue.push_back(Item {
-                label: item.label.clone(),
-                opened: item.opened.clone(),
-                minutes: 30,
-                flow: item.flow,
-            });
-            continue;
-        }
+    dfs_branch_and_bound(Exploration::new(&network, [30, 0]))
+        .best
+        .flow
+}
 
-        for (to_label, path) in paths {
-            let valve = valves.get(to_label).unwrap();
-
-            if item.minutes + path.len() + 1 <= 30 {
-                let mut opened = item.opened.clone();
-                opened.insert(to_label.clone());
-                let minutes = item.minutes + path.len() + 1;
-                let plus_flow = valve.flow_rate * (30 - minutes);
-                queue.push_back(Item {
-                    label: to_label.clone(),
-                    opened,
-                    minutes,
-                    flow: item.flow + plus_flow,
-                });
-            } else {
-                queue.push_back(Item {
-                    label: to_label.clone(),
-                    opened: item.opened.clone(),
-                    minutes: 30,
-                    flow: item.flow,
-                });
-            }
-        }
-    }
+pub fn part2(input: &Vec<&str>) -> usize {
+    let network = Network::new(input);
 
-    best_flow
+    dfs_branch_and_bound(Exploration::new(&network, [26, 26]))
+        .best
+        .flow
 }
 
-// pub fn part2(input: &Vec<&str>) -> usize {
-//     0
-// }
-
 #[cfg(test)]
 mod tests {
     use helpers::input_lines;
@@ -227,8 +246,8 @@ Valve JJ has flow rate=21; tunnel leads to valve II";
         assert_eq!(part1(&input()), 1651)
     }
 
-    // #[test]
-    // fn test_part2() {
-    //     assert_eq!(part2(&input()), 1707)
-    // }
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(&input()), 1707)
+    }
 }
@@ -1,7 +1,8 @@
-use std::collections::HashSet;
+use helpers::cycle::extrapolate;
+use helpers::fill::{flood_fill_points, Connectivity};
+use helpers::BitGrid;
 
-#[derive(Clone, Eq, PartialEq, Hash)]
-struct Coord(isize, isize);
+const CHAMBER_WIDTH: usize = 7;
 
 #[derive(Clone, Copy)]
 enum RockType {
@@ -12,144 +13,171 @@ enum RockType {
     Square,
 }
 
+impl RockType {
+    /// Row masks from the bottom up, bit 0 is the rock's left edge.
+    fn rows(&self) -> &'static [u64] {
+        match self {
+            RockType::Horizontal => &[0b1111],
+            RockType::Plus => &[0b010, 0b111, 0b010],
+            RockType::L => &[0b111, 0b100, 0b100],
+            RockType::Vertical => &[0b1, 0b1, 0b1, 0b1],
+            RockType::Square => &[0b11, 0b11],
+        }
+    }
+}
+
 #[derive(Clone)]
 struct Rock {
     r#type: RockType,
-    edge: Coord,
+    edge: (isize, usize),
 }
 
 impl Rock {
-    fn new(r#type: RockType, edge: Coord) -> Rock {
+    fn new(r#type: RockType, edge: (isize, usize)) -> Rock {
         Rock { r#type, edge }
     }
 
-    fn coords(&self) -> Vec<Coord> {
-        match self.r#type {
-            RockType::Horizontal => vec![
-                self.edge.clone(),
-                Coord(self.edge.0 + 1, self.edge.1),
-                Coord(self.edge.0 + 2, self.edge.1),
-                Coord(self.edge.0 + 3, self.edge.1),
-            ],
-            RockType::Plus => vec![
-                Coord(self.edge.0 + 1, self.edge.1 + 2),
-                Coord(self.edge.0, self.edge.1 + 1),
-                Coord(self.edge.0 + 1, self.edge.1 + 1),
-                Coord(self.edge.0 + 2, self.edge.1 + 1),
-                Coord(self.edge.0 + 1, self.edge.1),
-            ],
-            RockType::L => vec![
-                Coord(self.edge.0 + 2, self.edge.1 + 2),
-                Coord(self.edge.0 + 2, self.edge.1 + 1),
-                self.edge.clone(),
-                Coord(self.edge.0 + 1, self.edge.1),
-                Coord(self.edge.0 + 2, self.edge.1),
-            ],
-            RockType::Vertical => vec![
-                Coord(self.edge.0, self.edge.1 + 3),
-                Coord(self.edge.0, self.edge.1 + 2),
-                Coord(self.edge.0, self.edge.1 + 1),
-                self.edge.clone(),
-            ],
-            RockType::Square => vec![
-                Coord(self.edge.0, self.edge.1 + 1),
-                Coord(self.edge.0 + 1, self.edge.1 + 1),
-                self.edge.clone(),
-                Coord(self.edge.0 + 1, self.edge.1),
-            ],
-        }
+    fn collides(&self, chamber: &BitGrid) -> bool {
+        self.r#type
+            .rows()
+            .iter()
+            .enumerate()
+            .any(|(dy, &mask)| chamber.collides(self.edge.1 + dy, self.edge.0, mask))
     }
 
-    fn next(&self, diff: Coord) -> Self {
-        Rock::new(
-            self.r#type,
-            Coord(self.edge.0 + diff.0, self.edge.1 + diff.1),
-        )
+    fn settle(&self, chamber: &mut BitGrid) {
+        let rows = self.r#type.rows();
+        let top = self.edge.1 + rows.len();
+
+        if top > chamber.size.1 {
+            chamber.resize_height(top);
+        }
+
+        for (dy, &mask) in rows.iter().enumerate() {
+            chamber.or_mask(self.edge.1 + dy, self.edge.0 as usize, mask);
+        }
     }
 }
 
-fn create_next_rock(i: usize, y: isize) -> Rock {
-    let coord = Coord(2, y);
+fn create_next_rock(i: usize, y: usize) -> Rock {
+    let edge = (2, y);
 
     match i % 5 {
-        0 => Rock::new(RockType::Horizontal, coord),
-        1 => Rock::new(RockType::Plus, coord),
-        2 => Rock::new(RockType::L, coord),
-        3 => Rock::new(RockType::Vertical, coord),
-        4 => Rock::new(RockType::Square, coord),
+        0 => Rock::new(RockType::Horizontal, edge),
+        1 => Rock::new(RockType::Plus, edge),
+        2 => Rock::new(RockType::L, edge),
+        3 => Rock::new(RockType::Vertical, edge),
+        4 => Rock::new(RockType::Square, edge),
         _ => panic!("Invalid rock"),
     }
 }
 
-pub fn part1(jet_pattern: &Vec<&str>) -> usize {
-    let mut blocks = HashSet::new();
-    let mut height = -1;
-    let mut jet_pattern_index = 0;
+struct Tower<'a> {
+    // Row 0 is the bottom of the chamber, its height is the height of the tower
+    chamber: BitGrid,
+    jet_pattern: &'a [&'a str],
+    jet_pattern_index: usize,
+    rocks: usize,
+}
+
+impl<'a> Tower<'a> {
+    fn new(jet_pattern: &'a [&'a str]) -> Self {
+        Tower {
+            chamber: BitGrid::new((CHAMBER_WIDTH, 0)),
+            jet_pattern,
+            jet_pattern_index: 0,
+            rocks: 0,
+        }
+    }
 
-    for i in 0..2022 {
-        let mut rock = create_next_rock(i, height + 4);
+    fn height(&self) -> usize {
+        self.chamber.size.1
+    }
+
+    fn drop_rock(&mut self) {
+        let mut rock = create_next_rock(self.rocks, self.height() + 3);
+        self.rocks += 1;
 
-        'outer: loop {
+        loop {
             // Move by jets
-            let diff = match jet_pattern[jet_pattern_index % jet_pattern.len()] {
-                ">" => Coord(1, 0),
-                "<" => Coord(-1, 0),
+            let diff = match self.jet_pattern[self.jet_pattern_index] {
+                ">" => 1,
+                "<" => -1,
                 _ => panic!("Invalid jet pattern"),
             };
+            self.jet_pattern_index = (self.jet_pattern_index + 1) % self.jet_pattern.len();
 
-            let new_rock = rock.next(diff);
-            let coords = new_rock.coords();
-            let mut should_move = true;
-
-            for coord in coords {
-                if coord.0 < 0 || coord.0 > 6 || blocks.contains(&coord) {
-                    should_move = false;
-                    break;
-                }
+            let pushed = Rock::new(rock.r#type, (rock.edge.0 + diff, rock.edge.1));
+            if !pushed.collides(&self.chamber) {
+                rock = pushed;
+            }
 
-                if blocks.contains(&coord) {
-                    break 'outer;
-                }
+            // Move down
+            if rock.edge.1 == 0 {
+                break;
             }
 
-            if should_move {
-                rock = new_rock;
+            let fallen = Rock::new(rock.r#type, (rock.edge.0, rock.edge.1 - 1));
+            if fallen.collides(&self.chamber) {
+                break;
             }
 
-            jet_pattern_index += 1;
+            rock = fallen;
+        }
 
-            // Move down
-            let diff = Coord(0, -1);
-            let new_rock = rock.next(diff);
-            let coords = new_rock.coords();
-
-            for coord in coords {
-                if coord.1 < 0 || blocks.contains(&coord) {
-                    break 'outer;
-                }
+        rock.settle(&mut self.chamber);
+    }
+
+    /// Next rock, next jet and the air reachable from above the tower, as row masks from
+    /// the top down. Rocks can only ever fall into that air, so equal fingerprints have
+    /// equal futures.
+    fn fingerprint(&self) -> (usize, usize, Vec<u64>) {
+        let height = self.height() as isize;
+        // (x, depth), depth -1 being the open row just above the tower
+        let air = flood_fill_points((0, -1), Connectivity::Orthogonal, |&(x, depth)| {
+            if !(0..CHAMBER_WIDTH as isize).contains(&x) || !(-1..height).contains(&depth) {
+                return false;
             }
 
-            rock = new_rock;
-        }
+            let y = height - 1 - depth;
+            depth == -1 || !self.chamber.get((x as usize, y as usize))
+        });
 
-        for coord in rock.coords() {
-            if coord.1 > height {
-                height = coord.1;
+        let depth = air.iter().map(|&(_, depth)| depth + 1).max().unwrap_or(0);
+        let mut surface = vec![0; depth as usize];
+        for (x, depth) in air {
+            if depth >= 0 {
+                surface[depth as usize] |= 1 << x;
             }
-            blocks.insert(coord);
         }
+
+        (self.rocks % 5, self.jet_pattern_index, surface)
+    }
+}
+
+pub fn part1(jet_pattern: &[&str]) -> usize {
+    let mut tower = Tower::new(jet_pattern);
+
+    for _ in 0..2022 {
+        tower.drop_rock();
     }
 
-    height as usize + 1
+    tower.height()
 }
 
-// pub fn part2(jet_pattern: &Vec<&str>) -> usize {
-//     0
-// }
+pub fn part2(jet_pattern: &[&str]) -> usize {
+    let mut tower = Tower::new(jet_pattern);
+
+    extrapolate(1_000_000_000_000, |_| {
+        tower.drop_rock();
+
+        (tower.fingerprint(), tower.height())
+    })
+}
 
 #[cfg(test)]
 mod tests {
-    use helpers::{input_lines, split_input};
+    use helpers::split_input;
 
     use super::*;
 
@@ -163,8 +191,8 @@ mod tests {
         assert_eq!(part1(&input()), 3068)
     }
 
-    // #[test]
-    // fn test_part2() {
-    //     assert_eq!(part2(&input()), 1514285714288)
-    // }
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(&input()), 1514285714288)
+    }
 }
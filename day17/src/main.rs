@@ -4,5 +4,5 @@ fn main() {
     let input: Vec<&str> = split_input(include_str!("../input.txt"), "");
 
     run("part1", || day17::part1(&input));
-    // run("part2", || day17::part2(&input));
+    run("part2", || day17::part2(&input));
 }
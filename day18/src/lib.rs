@@ -1,4 +1,6 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::HashSet;
+
+use helpers::fill::{flood_fill_points, Connectivity};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Cube {
@@ -98,64 +100,23 @@ pub fn part2(input: &Vec<&str>) -> usize {
         }
     }
 
-    let mut sides_exposed = sides_exposed(&cubes);
-    let mut global_visited = HashSet::new();
-
-    for x in min_x..=max_x {
-        for y in min_y..=max_y {
-            'outer: for z in min_z..=max_z {
-                let mut queue = VecDeque::new();
-                let mut visited = HashSet::new();
-                let cube = Cube::new(x, y, z);
-
-                let mut path_sides_occupied = 0;
-
-                if cubes.contains(&cube) || global_visited.contains(&cube) {
-                    continue;
-                }
-
-                queue.push_back(cube);
-
-                while let Some(cube) = queue.pop_front() {
-                    if visited.contains(&cube) {
-                        continue;
-                    }
-
-                    visited.insert(cube);
-
-                    let mut sides_occuppied = 0;
-
-                    for neighbor in cube.neighbors() {
-                        if global_visited.contains(&neighbor)
-                            || neighbor.x < min_x
-                            || neighbor.y < min_y
-                            || neighbor.z < min_z
-                            || neighbor.x > max_x
-                            || neighbor.y > max_y
-                            || neighbor.z > max_z
-                        {
-                            continue 'outer;
-                        }
-
-                        if cubes.contains(&neighbor) {
-                            sides_occuppied += 1;
-                        } else {
-                            if !visited.contains(&neighbor) {
-                                queue.push_back(neighbor);
-                            }
-                        }
-                    }
-
-                    path_sides_occupied += sides_occuppied;
-                }
-
-                global_visited.extend(visited);
-                sides_exposed -= path_sides_occupied;
-            }
-        }
-    }
-
-    sides_exposed
+    // Air reachable from outside the droplet, within a box one cube larger than it
+    let outside = flood_fill_points(
+        (min_x - 1, min_y - 1, min_z - 1),
+        Connectivity::Orthogonal,
+        |&(x, y, z)| {
+            !cubes.contains(&Cube::new(x, y, z))
+                && (min_x - 1..=max_x + 1).contains(&x)
+                && (min_y - 1..=max_y + 1).contains(&y)
+                && (min_z - 1..=max_z + 1).contains(&z)
+        },
+    );
+
+    cubes
+        .iter()
+        .flat_map(|cube| cube.neighbors())
+        .filter(|neighbor| outside.contains(&(neighbor.x, neighbor.y, neighbor.z)))
+        .count()
 }
 
 #[cfg(test)]
@@ -1,43 +1,39 @@
-use std::ops::Range;
+use helpers::intervals::IntervalSet;
 
-fn parse_pair(line: &&&str) -> (Range<usize>, Range<usize>) {
-    let pair: Vec<Range<usize>> = line
+/// Section IDs are inclusive on both ends.
+fn parse_pair(line: &str) -> (IntervalSet<usize>, IntervalSet<usize>) {
+    let pair: Vec<IntervalSet<usize>> = line
         .split(",")
         .map(|section| {
-            let section = section
-                .split("-")
-                .map(|number| number.parse::<usize>().unwrap())
-                .collect::<Vec<usize>>();
+            let (start, end) = section.split_once("-").unwrap();
+            let start: usize = start.parse().unwrap();
+            let end: usize = end.parse().unwrap();
 
-            Range {
-                start: section[0],
-                end: section[1],
-            }
+            [start..=end].into_iter().collect()
         })
         .collect();
 
     (pair[0].to_owned(), pair[1].to_owned())
 }
 
-pub fn part1(input: &Vec<&str>) -> usize {
+pub fn part1(input: &[&str]) -> usize {
     input
-        .into_iter()
+        .iter()
         .filter(|line| {
-            let pair = parse_pair(line);
+            let (first, second) = parse_pair(line);
 
-            (pair.0.start <= pair.1.start && pair.0.end >= pair.1.end)
-                || (pair.0.start >= pair.1.start && pair.0.end <= pair.1.end)
+            first.is_superset(&second) || second.is_superset(&first)
         })
         .count()
 }
 
-pub fn part2(input: &Vec<&str>) -> usize {
+pub fn part2(input: &[&str]) -> usize {
     input
-        .into_iter()
+        .iter()
         .filter(|line| {
-            let pair = parse_pair(line);
+            let (first, second) = parse_pair(line);
 
-            pair.0.start <= pair.1.end && pair.1.start <= pair.0.end
+            !first.intersection(&second).is_empty()
         })
         .count()
 }
@@ -1,24 +1,14 @@
-use helpers::Grid;
+use helpers::{Direction, Grid};
 
 pub fn part1(input: &Grid<usize>) -> usize {
     input
         .iter()
         .filter(|(coords, &height)| {
-            // left
-            (0..coords.0)
-                .all(|x| *input.get((x, coords.1)) < height)
-
-            // right
-            || (coords.0 + 1..input.size.0)
-                .all(|x| *input.get((x, coords.1)) < height)
-
-            // top
-            || (0..coords.1)
-                .all(|y| *input.get((coords.0, y)) < height)
-
-            // bottom
-            || (coords.1 + 1..input.size.1)
-                .all(|y| *input.get((coords.0, y)) < height)
+            Direction::ORTHOGONAL.iter().any(|&direction| {
+                input
+                    .ray(*coords, direction)
+                    .all(|(_, &tree)| tree < height)
+            })
         })
         .count()
 }
@@ -27,49 +17,21 @@ pub fn part2(input: &Grid<usize>) -> usize {
     input
         .iter()
         .map(|(coords, &height)| {
-            let mut score = 1;
-            let mut viewing_distance = 0;
-
-            // left
-            for x in (0..coords.0).rev() {
-                viewing_distance += 1;
-                if *input.get((x, coords.1)) >= height {
-                    break;
-                }
-            }
-            score *= viewing_distance;
-            viewing_distance = 0;
-
-            // right
-            for x in coords.0 + 1..input.size.0 {
-                viewing_distance += 1;
-                if *input.get((x, coords.1)) >= height {
-                    break;
-                }
-            }
-            score *= viewing_distance;
-            viewing_distance = 0;
-
-            // top
-            for y in (0..coords.1).rev() {
-                viewing_distance += 1;
-                if *input.get((coords.0, y)) >= height {
-                    break;
-                }
-            }
-            score *= viewing_distance;
-            viewing_distance = 0;
+            Direction::ORTHOGONAL
+                .iter()
+                .map(|&direction| {
+                    let mut viewing_distance = 0;
 
-            // bottom
-            for y in coords.1 + 1..input.size.1 {
-                viewing_distance += 1;
-                if *input.get((coords.0, y)) >= height {
-                    break;
-                }
-            }
-            score *= viewing_distance;
+                    for (_, &tree) in input.ray(coords, direction) {
+                        viewing_distance += 1;
+                        if tree >= height {
+                            break;
+                        }
+                    }
 
-            return score;
+                    viewing_distance
+                })
+                .product()
         })
         .max()
         .unwrap()
@@ -0,0 +1,410 @@
+use std::collections::{HashMap, HashSet};
+use std::mem::swap;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::fill::{Connectivity, Lattice};
+use crate::{Coord, Grid, NeighborIter, Neighborhood};
+
+type Tracer<'a, T> = Box<dyn FnMut(usize, &Grid<T>) + 'a>;
+
+/// Double-buffered automaton over a `Grid`, `rule` computes the next value of a cell from
+/// its coordinate, current value and neighbours.
+pub struct Automaton<'a, T, R> {
+    grid: Grid<T>,
+    buffer: Grid<T>,
+    neighborhood: Neighborhood<'a>,
+    rule: R,
+    generation: usize,
+    tracer: Option<Tracer<'a, T>>,
+}
+
+impl<'a, T, R> Automaton<'a, T, R>
+where
+    T: Clone + PartialEq,
+    R: Fn(Coord, &T, NeighborIter<'_, T>) -> T,
+{
+    pub fn new(grid: Grid<T>, neighborhood: Neighborhood<'a>, rule: R) -> Self {
+        Automaton {
+            buffer: grid.clone(),
+            grid,
+            neighborhood,
+            rule,
+            generation: 0,
+            tracer: None,
+        }
+    }
+
+    /// Calls `tracer` with the initial grid and after every generation.
+    pub fn with_tracer<F>(mut self, mut tracer: F) -> Self
+    where
+        F: FnMut(usize, &Grid<T>) + 'a,
+    {
+        tracer(self.generation, &self.grid);
+        self.tracer = Some(Box::new(tracer));
+        self
+    }
+
+    pub fn grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    pub fn into_grid(self) -> Grid<T> {
+        self.grid
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Computes the next generation, returns whether any cell changed.
+    pub fn step(&mut self) -> bool {
+        let changed = Self::compute(
+            &self.grid,
+            self.buffer.cells_mut(),
+            0,
+            self.neighborhood,
+            &self.rule,
+        );
+
+        self.advance(changed)
+    }
+
+    /// Swaps in the computed generation and traces it.
+    fn advance(&mut self, changed: bool) -> bool {
+        swap(&mut self.grid, &mut self.buffer);
+        self.generation += 1;
+
+        if let Some(tracer) = self.tracer.as_mut() {
+            tracer(self.generation, &self.grid);
+        }
+
+        changed
+    }
+
+    /// Runs `generations` steps, returns how many of them changed the grid.
+    pub fn run(&mut self, generations: usize) -> usize {
+        (0..generations).filter(|_| self.step()).count()
+    }
+
+    /// Steps until a generation changes nothing and returns that generation's number.
+    /// Never returns if the automaton oscillates.
+    pub fn run_until_stable(&mut self) -> usize {
+        while self.step() {}
+
+        self.generation
+    }
+
+    /// Fills `cells`, the slice of the next generation starting at index `start`.
+    fn compute(
+        grid: &Grid<T>,
+        cells: &mut [T],
+        start: usize,
+        neighborhood: Neighborhood<'a>,
+        rule: &R,
+    ) -> bool {
+        let mut changed = false;
+
+        for (index, cell) in cells.iter_mut().enumerate() {
+            let index = start + index;
+            let coord = (index % grid.size.0, index / grid.size.0);
+            let current = grid.get(coord);
+
+            *cell = rule(
+                coord,
+                current,
+                grid.neighbors_iter_with(coord, neighborhood),
+            );
+            changed |= cell != current;
+        }
+
+        changed
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl<'a, T, R> Automaton<'a, T, R>
+where
+    T: Clone + PartialEq + Send + Sync,
+    R: Fn(Coord, &T, NeighborIter<'_, T>) -> T + Sync,
+{
+    /// Same as `step`, computing the rows of the next generation in parallel.
+    pub fn par_step(&mut self) -> bool {
+        let width = self.grid.size.0.max(1);
+        let (grid, neighborhood, rule) = (&self.grid, self.neighborhood, &self.rule);
+
+        let changed = self
+            .buffer
+            .cells_mut()
+            .par_chunks_mut(width)
+            .enumerate()
+            .map(|(y, cells)| Self::compute(grid, cells, y * width, neighborhood, rule))
+            // Not `any`, which would stop filling rows after the first change
+            .reduce(|| false, |a, b| a | b);
+
+        self.advance(changed)
+    }
+
+    /// Same as `run`, with parallel steps.
+    pub fn par_run(&mut self, generations: usize) -> usize {
+        (0..generations).filter(|_| self.par_step()).count()
+    }
+}
+
+/// Automaton over an unbounded set of live points, `rule` decides whether a point lives
+/// from its current state and its number of live neighbours.
+pub struct PointAutomaton<P, R> {
+    live: HashSet<P>,
+    connectivity: Connectivity,
+    rule: R,
+    generation: usize,
+}
+
+impl<P, R> PointAutomaton<P, R>
+where
+    P: Lattice,
+    R: Fn(bool, usize) -> bool,
+{
+    pub fn new(live: HashSet<P>, connectivity: Connectivity, rule: R) -> Self {
+        PointAutomaton {
+            live,
+            connectivity,
+            rule,
+            generation: 0,
+        }
+    }
+
+    pub fn live(&self) -> &HashSet<P> {
+        &self.live
+    }
+
+    pub fn into_live(self) -> HashSet<P> {
+        self.live
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Computes the next generation, returns whether any point changed.
+    pub fn step(&mut self) -> bool {
+        let mut counts: HashMap<P, usize> = HashMap::new();
+
+        for point in self.live.iter() {
+            counts.entry(*point).or_insert(0);
+
+            for neighbor in point.neighbors(self.connectivity) {
+                *counts.entry(neighbor).or_insert(0) += 1;
+            }
+        }
+
+        let next: HashSet<P> = counts
+            .into_iter()
+            .filter(|(point, count)| (self.rule)(self.live.contains(point), *count))
+            .map(|(point, _)| point)
+            .collect();
+
+        let changed = next != self.live;
+        self.live = next;
+        self.generation += 1;
+
+        changed
+    }
+
+    /// Runs `generations` steps, returns how many of them changed the set.
+    pub fn run(&mut self, generations: usize) -> usize {
+        (0..generations).filter(|_| self.step()).count()
+    }
+
+    /// Steps until a generation changes nothing and returns that generation's number.
+    /// Never returns if the automaton oscillates.
+    pub fn run_until_stable(&mut self) -> usize {
+        while self.step() {}
+
+        self.generation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use crate::{input_grid, Topology};
+
+    use super::*;
+
+    fn life(_: Coord, &alive: &char, neighbors: NeighborIter<'_, char>) -> char {
+        let live = neighbors.filter(|(_, &cell)| cell == '#').count();
+
+        match (alive, live) {
+            ('#', 2) | (_, 3) => '#',
+            _ => '.',
+        }
+    }
+
+    fn blinker() -> Grid<char> {
+        input_grid(
+            "
+.....
+..#..
+..#..
+..#..
+.....
+            ",
+        )
+    }
+
+    #[test]
+    fn test_step() {
+        let mut automaton = Automaton::new(blinker(), Neighborhood::Moore, life);
+
+        assert!(automaton.step());
+        assert_eq!(automaton.generation(), 1);
+        assert_eq!(
+            automaton.grid(),
+            &input_grid(
+                "
+.....
+.....
+.###.
+.....
+.....
+                "
+            )
+        );
+
+        assert!(automaton.step());
+        assert_eq!(automaton.grid(), &blinker());
+    }
+
+    #[test]
+    fn test_run() {
+        let mut automaton = Automaton::new(blinker(), Neighborhood::Moore, life);
+
+        assert_eq!(automaton.run(4), 4);
+        assert_eq!(automaton.into_grid(), blinker());
+    }
+
+    #[test]
+    fn test_run_until_stable() {
+        let grid = input_grid(
+            "
+....
+.##.
+.#..
+....
+            ",
+        );
+        let mut automaton = Automaton::new(grid, Neighborhood::Moore, life);
+
+        assert_eq!(automaton.run_until_stable(), 2);
+        assert_eq!(
+            automaton.grid(),
+            &input_grid(
+                "
+....
+.##.
+.##.
+....
+                "
+            )
+        );
+    }
+
+    #[test]
+    fn test_topology() {
+        let glider = input_grid(
+            "
+.#....
+..#...
+###...
+......
+......
+......
+            ",
+        )
+        .with_topology(Topology::Torus);
+        let mut automaton = Automaton::new(glider.clone(), Neighborhood::Moore, life);
+
+        // A glider crosses a 6x6 torus diagonally in 24 generations
+        automaton.run(24);
+
+        assert_eq!(automaton.grid(), &glider);
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn test_parallel() {
+        let grid = input_grid(
+            "
+.#......#
+..#...#..
+###..##..
+.........
+...###...
+#.......#
+.##...#..
+            ",
+        );
+
+        let mut sequential = Automaton::new(grid.clone(), Neighborhood::Moore, life);
+        let mut parallel = Automaton::new(grid, Neighborhood::Moore, life);
+
+        for _ in 0..10 {
+            assert_eq!(sequential.step(), parallel.par_step());
+            assert_eq!(sequential.grid(), parallel.grid());
+        }
+    }
+
+    #[test]
+    fn test_non_sync_rule() {
+        let calls = Cell::new(0);
+        let mut automaton =
+            Automaton::new(blinker(), Neighborhood::Moore, |coord, cell, neighbors| {
+                calls.set(calls.get() + 1);
+                life(coord, cell, neighbors)
+            });
+
+        automaton.run(2);
+
+        assert_eq!(automaton.grid(), &blinker());
+        assert_eq!(calls.get(), 2 * blinker().size.0 * blinker().size.1);
+    }
+
+    #[test]
+    fn test_tracer() {
+        let mut frames = Vec::new();
+
+        {
+            let mut automaton = Automaton::new(blinker(), Neighborhood::Moore, life)
+                .with_tracer(|generation, grid| frames.push((generation, grid.to_string())));
+            automaton.run(2);
+        }
+
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[0], (0, blinker().to_string()));
+        assert_eq!(frames[2], (2, blinker().to_string()));
+    }
+
+    #[test]
+    fn test_point_automaton() {
+        let rule = |alive: bool, live: usize| live == 3 || (alive && live == 2);
+        let blinker: HashSet<(isize, isize)> = HashSet::from([(0, -1), (0, 0), (0, 1)]);
+        let mut automaton = PointAutomaton::new(blinker.clone(), Connectivity::Full, rule);
+
+        assert!(automaton.step());
+        assert_eq!(automaton.live(), &HashSet::from([(-1, 0), (0, 0), (1, 0)]));
+        assert_eq!(automaton.run(3), 3);
+        assert_eq!(automaton.generation(), 4);
+        assert_eq!(automaton.into_live(), blinker);
+
+        let block: HashSet<(isize, isize, isize)> =
+            HashSet::from([(0, 0, 0), (1, 0, 0), (0, 1, 0), (1, 1, 0)]);
+        let mut automaton =
+            PointAutomaton::new(block, Connectivity::Full, |alive, live| alive && live >= 3);
+
+        assert_eq!(automaton.run_until_stable(), 1);
+        assert_eq!(automaton.live().len(), 4);
+    }
+}
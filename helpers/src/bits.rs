@@ -0,0 +1,733 @@
+use std::fmt::{Debug, Formatter};
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign};
+
+use crate::{Coord, Grid};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Set of indices below `64 * N`, stored inline.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BitSet<const N: usize> {
+    words: [u64; N],
+}
+
+impl<const N: usize> BitSet<N> {
+    pub const CAPACITY: usize = N * WORD_BITS;
+
+    pub fn new() -> Self {
+        BitSet { words: [0; N] }
+    }
+
+    /// Returns `true` if the index was not in the set.
+    pub fn insert(&mut self, index: usize) -> bool {
+        let (word, mask) = Self::locate(index);
+        let absent = self.words[word] & mask == 0;
+        self.words[word] |= mask;
+
+        absent
+    }
+
+    /// Returns `true` if the index was in the set.
+    pub fn remove(&mut self, index: usize) -> bool {
+        let (word, mask) = Self::locate(index);
+        let present = self.words[word] & mask != 0;
+        self.words[word] &= !mask;
+
+        present
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        if index >= Self::CAPACITY {
+            return false;
+        }
+
+        let (word, mask) = Self::locate(index);
+        self.words[word] & mask != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.words = [0; N];
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.words
+            .iter()
+            .zip(other.words.iter())
+            .all(|(a, b)| a & !b == 0)
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = *self;
+        for (word, other) in result.words.iter_mut().zip(other.words.iter()) {
+            *word &= !other;
+        }
+
+        result
+    }
+
+    /// Indices in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(index, &word)| {
+            let mut word = word;
+
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+
+                Some(index * WORD_BITS + bit)
+            })
+        })
+    }
+
+    fn locate(index: usize) -> (usize, u64) {
+        assert!(index < Self::CAPACITY);
+
+        (index / WORD_BITS, 1 << (index % WORD_BITS))
+    }
+}
+
+impl<const N: usize> Default for BitSet<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Debug for BitSet<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<const N: usize> FromIterator<usize> for BitSet<N> {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = BitSet::new();
+        for index in iter {
+            set.insert(index);
+        }
+
+        set
+    }
+}
+
+impl<const N: usize> BitOr for BitSet<N> {
+    type Output = Self;
+
+    fn bitor(mut self, other: Self) -> Self {
+        self |= other;
+        self
+    }
+}
+
+impl<const N: usize> BitOrAssign for BitSet<N> {
+    fn bitor_assign(&mut self, other: Self) {
+        for (word, other) in self.words.iter_mut().zip(other.words.iter()) {
+            *word |= other;
+        }
+    }
+}
+
+impl<const N: usize> BitAnd for BitSet<N> {
+    type Output = Self;
+
+    fn bitand(mut self, other: Self) -> Self {
+        self &= other;
+        self
+    }
+}
+
+impl<const N: usize> BitAndAssign for BitSet<N> {
+    fn bitand_assign(&mut self, other: Self) {
+        for (word, other) in self.words.iter_mut().zip(other.words.iter()) {
+            *word &= other;
+        }
+    }
+}
+
+/// Boolean grid packed one bit per cell, bit `x` of each row is the cell at `x`.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    pub size: (usize, usize),
+    words_per_row: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    pub fn new(size: (usize, usize)) -> Self {
+        let words_per_row = size.0.div_ceil(WORD_BITS);
+
+        BitGrid {
+            size,
+            words_per_row,
+            words: vec![0; words_per_row * size.1],
+        }
+    }
+
+    pub fn get(&self, coord: Coord) -> bool {
+        let (word, mask) = self.locate(coord);
+        self.words[word] & mask != 0
+    }
+
+    pub fn set(&mut self, coord: Coord, value: bool) {
+        let (word, mask) = self.locate(coord);
+
+        if value {
+            self.words[word] |= mask;
+        } else {
+            self.words[word] &= !mask;
+        }
+    }
+
+    /// Returns `true` if the cell was not set.
+    pub fn insert(&mut self, coord: Coord) -> bool {
+        let absent = !self.get(coord);
+        self.set(coord, true);
+
+        absent
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn row(&self, y: usize) -> &[u64] {
+        assert!(y < self.size.1);
+
+        &self.words[y * self.words_per_row..(y + 1) * self.words_per_row]
+    }
+
+    pub fn row_count_ones(&self, y: usize) -> usize {
+        self.row(y)
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Adds or removes rows at the bottom, new rows are empty.
+    pub fn resize_height(&mut self, height: usize) {
+        self.words.resize(self.words_per_row * height, 0);
+        self.size.1 = height;
+    }
+
+    /// Whether `mask` placed with its bit 0 at `(x, y)` hits a set cell or sticks out of the
+    /// grid horizontally. Rows past the grid's height are treated as empty.
+    pub fn collides(&self, y: usize, x: isize, mask: u64) -> bool {
+        if mask == 0 {
+            return false;
+        }
+
+        let highest = x + (WORD_BITS - 1 - mask.leading_zeros() as usize) as isize;
+        let lowest = x + mask.trailing_zeros() as isize;
+
+        if lowest < 0 || highest >= self.size.0 as isize {
+            return true;
+        }
+
+        if y >= self.size.1 {
+            return false;
+        }
+
+        // Bits below a negative `x` are known to be clear, so the mask can start at 0
+        let (x, mask) = if x < 0 {
+            (0, mask >> -x)
+        } else {
+            (x as usize, mask)
+        };
+
+        self.mask_words(x, mask)
+            .into_iter()
+            .any(|(word, bits)| bits != 0 && self.words[y * self.words_per_row + word] & bits != 0)
+    }
+
+    /// Sets the cells of `mask` placed with its bit 0 at `(x, y)`.
+    pub fn or_mask(&mut self, y: usize, x: usize, mask: u64) {
+        assert!(!self.collides_with_edges(x, mask));
+        assert!(y < self.size.1);
+
+        for (word, bits) in self.mask_words(x, mask) {
+            if bits != 0 {
+                self.words[y * self.words_per_row + word] |= bits;
+            }
+        }
+    }
+
+    /// Clears the cells of `mask` placed with its bit 0 at `(x, y)`.
+    pub fn clear_mask(&mut self, y: usize, x: usize, mask: u64) {
+        assert!(!self.collides_with_edges(x, mask));
+        assert!(y < self.size.1);
+
+        for (word, bits) in self.mask_words(x, mask) {
+            if bits != 0 {
+                self.words[y * self.words_per_row + word] &= !bits;
+            }
+        }
+    }
+
+    /// Moves every cell of row `y` by `offset` columns, cells moved past an edge are dropped.
+    pub fn shift_row(&mut self, y: usize, offset: isize) {
+        assert!(y < self.size.1);
+
+        let start = y * self.words_per_row;
+        let row = &mut self.words[start..start + self.words_per_row];
+        let distance = offset.unsigned_abs();
+        let (words, bits) = (distance / WORD_BITS, distance % WORD_BITS);
+
+        if words >= row.len() {
+            row.fill(0);
+            return;
+        }
+
+        if offset > 0 {
+            // Towards higher columns, so read lower words before they are overwritten
+            for index in (0..row.len()).rev() {
+                row[index] = match index.checked_sub(words) {
+                    Some(source) if bits == 0 => row[source],
+                    Some(0) => row[0] << bits,
+                    Some(source) => row[source] << bits | row[source - 1] >> (WORD_BITS - bits),
+                    None => 0,
+                };
+            }
+
+            // Bits past the width must stay clear
+            let tail = self.size.0 % WORD_BITS;
+            if tail != 0 {
+                row[row.len() - 1] &= (1 << tail) - 1;
+            }
+        } else {
+            for index in 0..row.len() {
+                let source = index + words;
+                row[index] = if source >= row.len() {
+                    0
+                } else if bits == 0 {
+                    row[source]
+                } else if source + 1 == row.len() {
+                    row[source] >> bits
+                } else {
+                    row[source] >> bits | row[source + 1] << (WORD_BITS - bits)
+                };
+            }
+        }
+    }
+
+    /// Coordinates of set cells in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = Coord> + '_ {
+        (0..self.size.1).flat_map(move |y| {
+            (0..self.size.0)
+                .filter(move |&x| self.get((x, y)))
+                .map(move |x| (x, y))
+        })
+    }
+
+    fn locate(&self, coord: Coord) -> (usize, u64) {
+        assert!(coord.0 < self.size.0);
+        assert!(coord.1 < self.size.1);
+
+        (
+            coord.1 * self.words_per_row + coord.0 / WORD_BITS,
+            1 << (coord.0 % WORD_BITS),
+        )
+    }
+
+    fn collides_with_edges(&self, x: usize, mask: u64) -> bool {
+        mask != 0 && x + (WORD_BITS - mask.leading_zeros() as usize) > self.size.0
+    }
+
+    /// Splits `mask` at column `x` into `(word index within the row, bits)` pairs. The second
+    /// pair has no bits when the mask fits in one word, and its index may then be past the row.
+    fn mask_words(&self, x: usize, mask: u64) -> [(usize, u64); 2] {
+        let word = x / WORD_BITS;
+        let shift = x % WORD_BITS;
+        let low = mask << shift;
+        let high = if shift == 0 {
+            0
+        } else {
+            mask >> (WORD_BITS - shift)
+        };
+
+        [(word, low), (word + 1, high)]
+    }
+}
+
+impl Debug for BitGrid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f)?;
+
+        for y in 0..self.size.1 {
+            for x in 0..self.size.0 {
+                write!(f, "{}", if self.get((x, y)) { '#' } else { '.' })?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl BitOr for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitor(self, other: Self) -> BitGrid {
+        let mut result = self.clone();
+        result |= other;
+        result
+    }
+}
+
+impl BitOrAssign<&BitGrid> for BitGrid {
+    fn bitor_assign(&mut self, other: &BitGrid) {
+        assert_eq!(self.size, other.size);
+
+        for (word, other) in self.words.iter_mut().zip(other.words.iter()) {
+            *word |= other;
+        }
+    }
+}
+
+impl BitAnd for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitand(self, other: Self) -> BitGrid {
+        let mut result = self.clone();
+        result &= other;
+        result
+    }
+}
+
+impl BitAndAssign<&BitGrid> for BitGrid {
+    fn bitand_assign(&mut self, other: &BitGrid) {
+        assert_eq!(self.size, other.size);
+
+        for (word, other) in self.words.iter_mut().zip(other.words.iter()) {
+            *word &= other;
+        }
+    }
+}
+
+impl From<&Grid<bool>> for BitGrid {
+    fn from(grid: &Grid<bool>) -> Self {
+        let mut bits = BitGrid::new(grid.size);
+
+        for (coord, &value) in grid.iter() {
+            if value {
+                bits.set(coord, true);
+            }
+        }
+
+        bits
+    }
+}
+
+impl From<&BitGrid> for Grid<bool> {
+    fn from(bits: &BitGrid) -> Self {
+        let cells = (0..bits.size.1)
+            .flat_map(|y| (0..bits.size.0).map(move |x| bits.get((x, y))))
+            .collect();
+
+        Grid::new(bits.size, cells)
+    }
+}
+
+/// Boolean voxel grid packed one bit per cell.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid3 {
+    pub size: (usize, usize, usize),
+    words: Vec<u64>,
+}
+
+impl BitGrid3 {
+    pub fn new(size: (usize, usize, usize)) -> Self {
+        BitGrid3 {
+            size,
+            words: vec![0; (size.0 * size.1 * size.2).div_ceil(WORD_BITS)],
+        }
+    }
+
+    pub fn get(&self, coord: (usize, usize, usize)) -> bool {
+        let (word, mask) = self.locate(coord);
+        self.words[word] & mask != 0
+    }
+
+    /// Out of bounds coordinates, including negative ones, are never set.
+    pub fn maybe_get(&self, coord: (isize, isize, isize)) -> bool {
+        if coord.0 < 0
+            || coord.1 < 0
+            || coord.2 < 0
+            || coord.0 >= self.size.0 as isize
+            || coord.1 >= self.size.1 as isize
+            || coord.2 >= self.size.2 as isize
+        {
+            return false;
+        }
+
+        self.get((coord.0 as usize, coord.1 as usize, coord.2 as usize))
+    }
+
+    pub fn set(&mut self, coord: (usize, usize, usize), value: bool) {
+        let (word, mask) = self.locate(coord);
+
+        if value {
+            self.words[word] |= mask;
+        } else {
+            self.words[word] &= !mask;
+        }
+    }
+
+    /// Returns `true` if the cell was not set.
+    pub fn insert(&mut self, coord: (usize, usize, usize)) -> bool {
+        let absent = !self.get(coord);
+        self.set(coord, true);
+
+        absent
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, usize)> + '_ {
+        let (width, height, _) = self.size;
+
+        self.words
+            .iter()
+            .enumerate()
+            .flat_map(move |(index, &word)| {
+                let mut word = word;
+
+                std::iter::from_fn(move || {
+                    if word == 0 {
+                        return None;
+                    }
+
+                    let bit = index * WORD_BITS + word.trailing_zeros() as usize;
+                    word &= word - 1;
+
+                    Some((bit % width, bit / width % height, bit / (width * height)))
+                })
+            })
+    }
+
+    fn locate(&self, coord: (usize, usize, usize)) -> (usize, u64) {
+        assert!(coord.0 < self.size.0);
+        assert!(coord.1 < self.size.1);
+        assert!(coord.2 < self.size.2);
+
+        let bit = coord.0 + coord.1 * self.size.0 + coord.2 * self.size.0 * self.size.1;
+
+        (bit / WORD_BITS, 1 << (bit % WORD_BITS))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bit_set() {
+        let mut set: BitSet<2> = BitSet::new();
+
+        assert!(set.is_empty());
+        assert!(set.insert(3));
+        assert!(set.insert(100));
+        assert!(!set.insert(3));
+        assert!(set.contains(100));
+        assert!(!set.contains(4));
+        assert!(!set.contains(1000));
+        assert_eq!(set.len(), 2);
+        assert_eq!(set.iter().collect::<Vec<usize>>(), vec![3, 100]);
+
+        assert!(set.remove(3));
+        assert!(!set.remove(3));
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn test_bit_set_operations() {
+        let a: BitSet<1> = [1, 2, 3].into_iter().collect();
+        let b: BitSet<1> = [3, 4].into_iter().collect();
+
+        assert_eq!((a | b).iter().collect::<Vec<usize>>(), vec![1, 2, 3, 4]);
+        assert_eq!((a & b).iter().collect::<Vec<usize>>(), vec![3]);
+        assert_eq!(a.difference(&b).iter().collect::<Vec<usize>>(), vec![1, 2]);
+        assert!((a & b).is_subset(&a));
+        assert!(!a.is_subset(&b));
+        assert_eq!(format!("{:?}", b), "{3, 4}");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bit_set_out_of_capacity() {
+        BitSet::<1>::new().insert(64);
+    }
+
+    #[test]
+    fn test_bit_grid() {
+        let mut grid = BitGrid::new((70, 3));
+
+        assert!(grid.insert((0, 0)));
+        assert!(grid.insert((69, 2)));
+        assert!(!grid.insert((69, 2)));
+        assert!(grid.get((69, 2)));
+        assert!(!grid.get((68, 2)));
+        assert_eq!(grid.count_ones(), 2);
+        assert_eq!(grid.row_count_ones(2), 1);
+        assert_eq!(grid.row(2), &[0, 1 << 5]);
+        assert_eq!(grid.iter().collect::<Vec<Coord>>(), vec![(0, 0), (69, 2)]);
+
+        grid.set((0, 0), false);
+        assert_eq!(grid.count_ones(), 1);
+    }
+
+    #[test]
+    fn test_bit_grid_masks() {
+        let mut grid = BitGrid::new((7, 2));
+        grid.or_mask(0, 2, 0b1111);
+
+        assert_eq!(grid.row(0), &[0b0111100]);
+        assert!(grid.collides(0, 0, 0b100));
+        assert!(!grid.collides(0, 0, 0b11));
+        assert!(!grid.collides(1, 2, 0b1111));
+        assert!(!grid.collides(5, 2, 0b1111));
+        // Sticking out of the sides
+        assert!(grid.collides(1, -1, 0b1));
+        assert!(grid.collides(1, 4, 0b1111));
+        assert!(!grid.collides(1, 3, 0b1111));
+        assert!(!grid.collides(0, -1, 0b10));
+        assert!(grid.collides(0, -2, 0b10000));
+
+        grid.clear_mask(0, 3, 0b11);
+        assert_eq!(grid.row(0), &[0b0100100]);
+    }
+
+    #[test]
+    fn test_bit_grid_masks_across_words() {
+        let mut grid = BitGrid::new((100, 1));
+        grid.or_mask(0, 62, 0b111);
+
+        assert!(grid.get((62, 0)));
+        assert!(grid.get((64, 0)));
+        assert!(grid.collides(0, 64, 0b1));
+        assert!(!grid.collides(0, 65, 0b1));
+        assert_eq!(grid.row_count_ones(0), 3);
+    }
+
+    #[test]
+    fn test_bit_grid_shift_row() {
+        let mut grid = BitGrid::new((7, 1));
+        grid.or_mask(0, 0, 0b1000011);
+
+        grid.shift_row(0, 1);
+        assert_eq!(grid.row(0), &[0b0000110]);
+
+        grid.shift_row(0, -2);
+        assert_eq!(grid.row(0), &[0b0000001]);
+    }
+
+    #[test]
+    fn test_bit_grid_shift_row_across_words() {
+        let cells = [0, 1, 60, 63, 64, 127, 128, 149];
+        let mut grid = BitGrid::new((150, 1));
+        for x in cells {
+            grid.set((x, 0), true);
+        }
+
+        let mut shifted = grid.clone();
+        shifted.shift_row(0, 5);
+        assert_eq!(
+            shifted.iter().map(|(x, _)| x).collect::<Vec<usize>>(),
+            vec![5, 6, 65, 68, 69, 132, 133]
+        );
+
+        for offset in (-160..=160).step_by(7) {
+            let mut shifted = grid.clone();
+            shifted.shift_row(0, offset);
+
+            let expected: Vec<usize> = cells
+                .iter()
+                .map(|&x| x as isize + offset)
+                .filter(|&x| (0..150).contains(&x))
+                .map(|x| x as usize)
+                .collect();
+
+            assert_eq!(
+                shifted.iter().map(|(x, _)| x).collect::<Vec<usize>>(),
+                expected,
+                "offset {}",
+                offset
+            );
+            assert_eq!(shifted.row_count_ones(0), expected.len());
+        }
+    }
+
+    #[test]
+    fn test_bit_grid_resize_height() {
+        let mut grid = BitGrid::new((7, 1));
+        grid.set((3, 0), true);
+        grid.resize_height(3);
+
+        assert_eq!(grid.size, (7, 3));
+        assert!(grid.get((3, 0)));
+        assert!(!grid.get((3, 2)));
+    }
+
+    #[test]
+    fn test_bit_grid_operations() {
+        let mut a = BitGrid::new((3, 2));
+        a.set((0, 0), true);
+        a.set((1, 1), true);
+        let mut b = BitGrid::new((3, 2));
+        b.set((1, 1), true);
+        b.set((2, 1), true);
+
+        assert_eq!((&a | &b).count_ones(), 3);
+        assert_eq!((&a & &b).iter().collect::<Vec<Coord>>(), vec![(1, 1)]);
+    }
+
+    #[test]
+    fn test_bit_grid_conversions() {
+        let grid = Grid::new((3, 2), vec![true, false, false, false, true, true]);
+        let bits = BitGrid::from(&grid);
+
+        assert_eq!(bits.count_ones(), 3);
+        assert_eq!(Grid::from(&bits), grid);
+    }
+
+    #[test]
+    fn test_bit_grid_3d() {
+        let mut grid = BitGrid3::new((5, 4, 3));
+
+        assert!(grid.insert((4, 3, 2)));
+        assert!(grid.insert((1, 0, 1)));
+        assert!(!grid.insert((1, 0, 1)));
+        assert!(grid.get((4, 3, 2)));
+        assert!(!grid.get((4, 3, 1)));
+        assert!(grid.maybe_get((1, 0, 1)));
+        assert!(!grid.maybe_get((-1, 0, 1)));
+        assert!(!grid.maybe_get((5, 0, 1)));
+        assert_eq!(grid.count_ones(), 2);
+        assert_eq!(
+            grid.iter().collect::<Vec<(usize, usize, usize)>>(),
+            vec![(1, 0, 1), (4, 3, 2)]
+        );
+    }
+}
@@ -0,0 +1,224 @@
+use std::fmt::{Display, Formatter};
+
+use crate::{Coord, Grid};
+
+/// Size and row-major cells, `None` where no value was given.
+type Placed<T> = ((usize, usize), Vec<Option<T>>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridBuildError {
+    /// No value was given for this cell and there is nothing to fill it with.
+    Missing(Coord),
+    Duplicate(Coord),
+    /// The coordinate lies outside the size set with `GridBuilder::with_size`.
+    OutOfBounds(Coord),
+}
+
+impl Display for GridBuildError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridBuildError::Missing(coord) => write!(f, "No value for {:?}", coord),
+            GridBuildError::Duplicate(coord) => write!(f, "Several values for {:?}", coord),
+            GridBuildError::OutOfBounds(coord) => write!(f, "{:?} is out of bounds", coord),
+        }
+    }
+}
+
+impl std::error::Error for GridBuildError {}
+
+/// Places values at their coordinates in any order. Unless set, the size is the smallest
+/// one holding every coordinate.
+#[derive(Debug, Clone)]
+pub struct GridBuilder<T> {
+    size: Option<(usize, usize)>,
+    values: Vec<(Coord, T)>,
+}
+
+impl<T> GridBuilder<T> {
+    pub fn new() -> Self {
+        GridBuilder {
+            size: None,
+            values: Vec::new(),
+        }
+    }
+
+    pub fn with_size(mut self, size: (usize, usize)) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    pub fn insert(&mut self, coord: Coord, value: T) {
+        self.values.push((coord, value));
+    }
+
+    /// Fails if any cell has no value.
+    pub fn build(self) -> Result<Grid<T>, GridBuildError> {
+        let (size, cells) = self.place()?;
+
+        let cells = cells
+            .into_iter()
+            .enumerate()
+            .map(|(index, cell)| {
+                cell.ok_or(GridBuildError::Missing((index % size.0, index / size.0)))
+            })
+            .collect::<Result<Vec<T>, GridBuildError>>()?;
+
+        Ok(Grid::new(size, cells))
+    }
+
+    /// Fills cells without a value with `fill`.
+    pub fn build_with(self, fill: T) -> Result<Grid<T>, GridBuildError>
+    where
+        T: Clone,
+    {
+        let (size, cells) = self.place()?;
+        let cells = cells
+            .into_iter()
+            .map(|cell| cell.unwrap_or_else(|| fill.clone()))
+            .collect();
+
+        Ok(Grid::new(size, cells))
+    }
+
+    pub fn build_with_default(self) -> Result<Grid<T>, GridBuildError>
+    where
+        T: Clone + Default,
+    {
+        self.build_with(T::default())
+    }
+
+    fn place(self) -> Result<Placed<T>, GridBuildError> {
+        let size = self.size.unwrap_or_else(|| {
+            self.values.iter().fold((0, 0), |size, ((x, y), _)| {
+                (size.0.max(x + 1), size.1.max(y + 1))
+            })
+        });
+
+        let mut cells: Vec<Option<T>> = (0..size.0 * size.1).map(|_| None).collect();
+
+        for (coord, value) in self.values {
+            if coord.0 >= size.0 || coord.1 >= size.1 {
+                return Err(GridBuildError::OutOfBounds(coord));
+            }
+
+            let cell = &mut cells[coord.0 + coord.1 * size.0];
+            if cell.is_some() {
+                return Err(GridBuildError::Duplicate(coord));
+            }
+
+            *cell = Some(value);
+        }
+
+        Ok((size, cells))
+    }
+}
+
+impl<T> Default for GridBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Extend<(Coord, T)> for GridBuilder<T> {
+    fn extend<I: IntoIterator<Item = (Coord, T)>>(&mut self, iter: I) {
+        self.values.extend(iter);
+    }
+}
+
+impl<T> FromIterator<(Coord, T)> for GridBuilder<T> {
+    fn from_iter<I: IntoIterator<Item = (Coord, T)>>(iter: I) -> Self {
+        let mut builder = GridBuilder::new();
+        builder.extend(iter);
+
+        builder
+    }
+}
+
+/// Panics unless every cell gets exactly one value, use `GridBuilder` to handle gaps.
+impl<T> FromIterator<(Coord, T)> for Grid<T> {
+    fn from_iter<I: IntoIterator<Item = (Coord, T)>>(iter: I) -> Self {
+        match iter.into_iter().collect::<GridBuilder<T>>().build() {
+            Ok(grid) => grid,
+            Err(error) => panic!("{}", error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn test_out_of_order() {
+        let values = [((1, 1), 'd'), ((0, 1), 'c'), ((1, 0), 'b'), ((0, 0), 'a')];
+        let grid: Grid<char> = values.into_iter().collect();
+
+        assert_eq!(grid, Grid::new((2, 2), vec!['a', 'b', 'c', 'd']));
+    }
+
+    #[test]
+    fn test_from_hash_map() {
+        let grid = Grid::new((3, 2), (0..6).collect());
+        let map: HashMap<Coord, usize> = grid.iter().map(|(coord, &cell)| (coord, cell)).collect();
+
+        assert_eq!(map.into_iter().collect::<Grid<usize>>(), grid);
+    }
+
+    #[test]
+    #[should_panic(expected = "No value for (1, 0)")]
+    fn test_from_iter_with_gaps() {
+        let _: Grid<char> = [((0, 0), 'a'), ((1, 1), 'b')].into_iter().collect();
+    }
+
+    #[test]
+    fn test_sparse() {
+        let builder: GridBuilder<char> = [((2, 0), '#'), ((0, 1), '#')].into_iter().collect();
+
+        assert_eq!(
+            builder.clone().build(),
+            Err(GridBuildError::Missing((0, 0)))
+        );
+        assert_eq!(
+            builder.clone().build_with('.').unwrap().to_string(),
+            "..#\n#.."
+        );
+        assert_eq!(
+            builder.build_with_default().unwrap().to_string(),
+            "\0\0#\n#\0\0"
+        );
+    }
+
+    #[test]
+    fn test_with_size() {
+        let mut builder = GridBuilder::new().with_size((3, 3));
+        builder.insert((1, 1), 1);
+
+        let grid = builder.clone().build_with(0).unwrap();
+        assert_eq!(grid.size, (3, 3));
+        assert_eq!(grid[(1, 1)], 1);
+
+        builder.insert((3, 0), 1);
+        assert_eq!(
+            builder.build_with(0),
+            Err(GridBuildError::OutOfBounds((3, 0)))
+        );
+    }
+
+    #[test]
+    fn test_duplicate() {
+        let mut builder = GridBuilder::new();
+        builder.extend([((0, 0), 1), ((0, 0), 2)]);
+
+        assert_eq!(builder.build(), Err(GridBuildError::Duplicate((0, 0))));
+    }
+
+    #[test]
+    fn test_empty() {
+        let grid: Grid<usize> = std::iter::empty().collect();
+
+        assert_eq!(grid.size, (0, 0));
+        assert!(grid.is_empty());
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The sequence repeats from index `start` with period `length`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Index of the first occurrence of the state found at `index`.
+    pub fn reduce(&self, index: usize) -> usize {
+        if index < self.start {
+            index
+        } else {
+            self.start + (index - self.start) % self.length
+        }
+    }
+}
+
+/// Floyd's tortoise and hare on the sequence `initial, step(initial), ...`, which must
+/// eventually repeat.
+pub fn floyd<S, F>(initial: S, mut step: F) -> Cycle
+where
+    S: Clone + PartialEq,
+    F: FnMut(&S) -> S,
+{
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let halfway = step(&hare);
+        hare = step(&halfway);
+    }
+
+    let mut start = 0;
+    tortoise = initial;
+
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise);
+
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Brent's algorithm, same result as `floyd` with fewer calls to `step`.
+pub fn brent<S, F>(initial: S, mut step: F) -> Cycle
+where
+    S: Clone + PartialEq,
+    F: FnMut(&S) -> S,
+{
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+
+        hare = step(&hare);
+        length += 1;
+    }
+
+    let mut start = 0;
+    tortoise = initial.clone();
+    hare = initial;
+
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Metric after `steps` steps of a simulation too long to run, assuming it eventually loops.
+///
+/// `step(index)` runs step `index`, counting from 0, and returns a fingerprint of the
+/// resulting state and the metric accumulated so far, e.g. the height of a tower. Once a
+/// fingerprint repeats, the metric gained over one period is added for each remaining one.
+pub fn extrapolate<K, F>(steps: usize, mut step: F) -> usize
+where
+    K: Hash + Eq,
+    F: FnMut(usize) -> (K, usize),
+{
+    let mut seen: HashMap<K, usize> = HashMap::new();
+    let mut metrics = Vec::new();
+
+    for index in 0..steps {
+        let (fingerprint, metric) = step(index);
+        metrics.push(metric);
+
+        if let Some(&previous) = seen.get(&fingerprint) {
+            let cycle_length = index - previous;
+            let remaining = steps - 1 - index;
+            let gain = metric - metrics[previous];
+            let rest = metrics[previous + remaining % cycle_length] - metrics[previous];
+
+            return metric + remaining / cycle_length * gain + rest;
+        }
+
+        seen.insert(fingerprint, index);
+    }
+
+    metrics.last().copied().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0, 1, 2, 3, 4, 5, 6, 2, 3, 4, ...
+    fn next(&value: &usize) -> usize {
+        if value == 6 {
+            2
+        } else {
+            value + 1
+        }
+    }
+
+    #[test]
+    fn test_floyd() {
+        assert_eq!(
+            floyd(0, next),
+            Cycle {
+                start: 2,
+                length: 5
+            }
+        );
+        assert_eq!(
+            floyd(3, next),
+            Cycle {
+                start: 0,
+                length: 5
+            }
+        );
+    }
+
+    #[test]
+    fn test_brent() {
+        assert_eq!(
+            brent(0, next),
+            Cycle {
+                start: 2,
+                length: 5
+            }
+        );
+        assert_eq!(
+            brent(7, |&value| value),
+            Cycle {
+                start: 0,
+                length: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_reduce() {
+        let cycle = brent(0, next);
+
+        assert_eq!(cycle.reduce(1), 1);
+        assert_eq!(cycle.reduce(7), 2);
+        assert_eq!(cycle.reduce(1_000_000), 5);
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        // Gains 1, 2, 3 on steps 0 to 2, then 10 and 20 forever
+        let gain = |index: usize| match index {
+            0..=2 => index + 1,
+            _ if index % 2 == 1 => 10,
+            _ => 20,
+        };
+        let simulate = |steps: usize| -> usize { (0..steps).map(gain).sum() };
+
+        let mut total = 0;
+        let mut run = |index: usize| {
+            total += gain(index);
+            let fingerprint = if index < 3 { index } else { 3 + index % 2 };
+
+            (fingerprint, total)
+        };
+
+        assert_eq!(extrapolate(1000, &mut run), simulate(1000));
+    }
+
+    #[test]
+    fn test_extrapolate_before_any_cycle() {
+        assert_eq!(extrapolate(4, |index| (index, index * 2)), 6);
+        assert_eq!(extrapolate(0, |index| (index, index)), 0);
+    }
+}
@@ -0,0 +1,136 @@
+use crate::Coord;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction {
+    Left,
+    TopLeft,
+    Top,
+    TopRight,
+    Right,
+    BottomRight,
+    Bottom,
+    BottomLeft,
+}
+
+impl Direction {
+    pub const ORTHOGONAL: [Direction; 4] = [
+        Direction::Left,
+        Direction::Top,
+        Direction::Right,
+        Direction::Bottom,
+    ];
+
+    pub const ALL: [Direction; 8] = [
+        Direction::Left,
+        Direction::TopLeft,
+        Direction::Top,
+        Direction::TopRight,
+        Direction::Right,
+        Direction::BottomRight,
+        Direction::Bottom,
+        Direction::BottomLeft,
+    ];
+
+    pub fn offset(&self) -> (isize, isize) {
+        match self {
+            Direction::Left => (-1, 0),
+            Direction::TopLeft => (-1, -1),
+            Direction::Top => (0, -1),
+            Direction::TopRight => (1, -1),
+            Direction::Right => (1, 0),
+            Direction::BottomRight => (1, 1),
+            Direction::Bottom => (0, 1),
+            Direction::BottomLeft => (-1, 1),
+        }
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        let offset = self.offset();
+        offset.0 != 0 && offset.1 != 0
+    }
+
+    pub fn turn_left(&self) -> Self {
+        self.rotate(-2)
+    }
+
+    pub fn turn_right(&self) -> Self {
+        self.rotate(2)
+    }
+
+    pub fn opposite(&self) -> Self {
+        self.rotate(4)
+    }
+
+    /// Rotates clockwise by `steps` of 45 degrees, negative steps rotate counterclockwise.
+    pub fn rotate(&self, steps: isize) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|direction| direction == self)
+            .unwrap();
+
+        Self::ALL[(index as isize + steps).rem_euclid(8) as usize]
+    }
+
+    /// Moves `coord` one step, returns `None` when it would go below zero.
+    pub fn apply(&self, coord: Coord) -> Option<Coord> {
+        let offset = self.offset();
+
+        Some((
+            coord.0.checked_add_signed(offset.0)?,
+            coord.1.checked_add_signed(offset.1)?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turn() {
+        assert_eq!(Direction::Top.turn_right(), Direction::Right);
+        assert_eq!(Direction::Top.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.turn_left(), Direction::Bottom);
+        assert_eq!(Direction::TopRight.turn_right(), Direction::BottomRight);
+        assert_eq!(Direction::BottomLeft.turn_left(), Direction::BottomRight);
+    }
+
+    #[test]
+    fn test_opposite() {
+        for direction in Direction::ALL {
+            let offset = direction.offset();
+            assert_eq!(direction.opposite().offset(), (-offset.0, -offset.1));
+            assert_eq!(direction.opposite().opposite(), direction);
+        }
+    }
+
+    #[test]
+    fn test_rotate() {
+        assert_eq!(Direction::Top.rotate(1), Direction::TopRight);
+        assert_eq!(Direction::Left.rotate(-1), Direction::BottomLeft);
+        assert_eq!(Direction::Left.rotate(8), Direction::Left);
+    }
+
+    #[test]
+    fn test_is_diagonal() {
+        assert_eq!(
+            Direction::ALL
+                .iter()
+                .filter(|direction| direction.is_diagonal())
+                .count(),
+            4
+        );
+        assert!(Direction::ORTHOGONAL
+            .iter()
+            .all(|direction| !direction.is_diagonal()));
+    }
+
+    #[test]
+    fn test_apply() {
+        assert_eq!(Direction::Right.apply((2, 3)), Some((3, 3)));
+        assert_eq!(Direction::TopLeft.apply((2, 3)), Some((1, 2)));
+        assert_eq!(Direction::Left.apply((0, 3)), None);
+        assert_eq!(Direction::Top.apply((2, 0)), None);
+    }
+}
@@ -0,0 +1,358 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::search::bfs_all;
+use crate::{Coord, Grid};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Connectivity {
+    /// 4 neighbours in 2D, 6 in 3D.
+    Orthogonal,
+    /// 8 neighbours in 2D, 26 in 3D.
+    Full,
+}
+
+/// Unbounded integer coordinates, used for sparse maps and voxel sets.
+pub trait Lattice: Copy + Eq + Hash {
+    fn neighbors(&self, connectivity: Connectivity) -> Vec<Self>;
+
+    fn min_corner(&self, other: &Self) -> Self;
+
+    fn max_corner(&self, other: &Self) -> Self;
+}
+
+impl Lattice for (isize, isize) {
+    fn neighbors(&self, connectivity: Connectivity) -> Vec<Self> {
+        let mut neighbors = Vec::with_capacity(8);
+
+        for y in -1isize..=1 {
+            for x in -1isize..=1 {
+                let distance = x.abs() + y.abs();
+
+                if distance == 1 || (distance == 2 && connectivity == Connectivity::Full) {
+                    neighbors.push((self.0 + x, self.1 + y));
+                }
+            }
+        }
+
+        neighbors
+    }
+
+    fn min_corner(&self, other: &Self) -> Self {
+        (self.0.min(other.0), self.1.min(other.1))
+    }
+
+    fn max_corner(&self, other: &Self) -> Self {
+        (self.0.max(other.0), self.1.max(other.1))
+    }
+}
+
+impl Lattice for (isize, isize, isize) {
+    fn neighbors(&self, connectivity: Connectivity) -> Vec<Self> {
+        let mut neighbors = Vec::with_capacity(26);
+
+        for z in -1isize..=1 {
+            for y in -1isize..=1 {
+                for x in -1isize..=1 {
+                    let distance = x.abs() + y.abs() + z.abs();
+
+                    if distance == 1 || (distance > 1 && connectivity == Connectivity::Full) {
+                        neighbors.push((self.0 + x, self.1 + y, self.2 + z));
+                    }
+                }
+            }
+        }
+
+        neighbors
+    }
+
+    fn min_corner(&self, other: &Self) -> Self {
+        (
+            self.0.min(other.0),
+            self.1.min(other.1),
+            self.2.min(other.2),
+        )
+    }
+
+    fn max_corner(&self, other: &Self) -> Self {
+        (
+            self.0.max(other.0),
+            self.1.max(other.1),
+            self.2.max(other.2),
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Component<P> {
+    pub size: usize,
+    /// Inclusive `(min, max)` corners.
+    pub bounds: (P, P),
+}
+
+impl<P: Copy> Component<P> {
+    fn new(point: P) -> Self {
+        Component {
+            size: 0,
+            bounds: (point, point),
+        }
+    }
+}
+
+/// Cells reachable from `start` through passable cells, `start` included if passable.
+pub fn flood_fill<T, F>(
+    grid: &Grid<T>,
+    start: Coord,
+    connectivity: Connectivity,
+    mut passable: F,
+) -> HashSet<Coord>
+where
+    F: FnMut(Coord, &T) -> bool,
+{
+    if !passable(start, grid.get(start)) {
+        return HashSet::new();
+    }
+
+    bfs_all(start, |&coord| {
+        grid.neighbors_iter(coord, connectivity == Connectivity::Full)
+            .filter(|&(coord, cell)| passable(coord, cell))
+            .map(|(coord, _)| coord)
+            .collect::<Vec<Coord>>()
+    })
+    .into_keys()
+    .collect()
+}
+
+/// Labels every passable cell with its component id, ids are assigned in row-major order.
+pub fn label_components<T, F>(
+    grid: &Grid<T>,
+    connectivity: Connectivity,
+    mut passable: F,
+) -> (Grid<Option<usize>>, Vec<Component<Coord>>)
+where
+    F: FnMut(Coord, &T) -> bool,
+{
+    let mut labels = Grid::new(grid.size, vec![None; grid.len()]);
+    let mut components = Vec::new();
+
+    for (coord, cell) in grid.iter() {
+        if labels.get(coord).is_some() || !passable(coord, cell) {
+            continue;
+        }
+
+        let id = components.len();
+        let mut component = Component::new(coord);
+
+        for coord in flood_fill(grid, coord, connectivity, &mut passable) {
+            labels.set(coord, Some(id));
+            component.size += 1;
+            component.bounds = (
+                (
+                    component.bounds.0 .0.min(coord.0),
+                    component.bounds.0 .1.min(coord.1),
+                ),
+                (
+                    component.bounds.1 .0.max(coord.0),
+                    component.bounds.1 .1.max(coord.1),
+                ),
+            );
+        }
+
+        components.push(component);
+    }
+
+    (labels, components)
+}
+
+/// Points reachable from `start`, `passable` must reject all but a finite region.
+pub fn flood_fill_points<P, F>(start: P, connectivity: Connectivity, mut passable: F) -> HashSet<P>
+where
+    P: Lattice,
+    F: FnMut(&P) -> bool,
+{
+    if !passable(&start) {
+        return HashSet::new();
+    }
+
+    bfs_all(start, |point| {
+        point
+            .neighbors(connectivity)
+            .into_iter()
+            .filter(|neighbor| passable(neighbor))
+            .collect::<Vec<P>>()
+    })
+    .into_keys()
+    .collect()
+}
+
+/// Labels a sparse set of points, such as the keys of a `HashMap` or a set of voxels.
+pub fn label_points<P, I>(
+    points: I,
+    connectivity: Connectivity,
+) -> (HashMap<P, usize>, Vec<Component<P>>)
+where
+    P: Lattice,
+    I: IntoIterator<Item = P>,
+{
+    let points: Vec<P> = points.into_iter().collect();
+    let set: HashSet<P> = points.iter().copied().collect();
+    let mut labels = HashMap::new();
+    let mut components = Vec::new();
+
+    for point in points {
+        if labels.contains_key(&point) {
+            continue;
+        }
+
+        let id = components.len();
+        let mut component = Component::new(point);
+
+        for point in flood_fill_points(point, connectivity, |point| set.contains(point)) {
+            labels.insert(point, id);
+            component.size += 1;
+            component.bounds = (
+                component.bounds.0.min_corner(&point),
+                component.bounds.1.max_corner(&point),
+            );
+        }
+
+        components.push(component);
+    }
+
+    (labels, components)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input_grid;
+
+    use super::*;
+
+    fn create_map() -> Grid<char> {
+        input_grid(
+            "
+##..#
+#...#
+..#..
+...##
+#..##
+            ",
+        )
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let map = create_map();
+
+        let filled = flood_fill(&map, (2, 0), Connectivity::Orthogonal, |_, &c| c == '.');
+        assert_eq!(filled.len(), 14);
+        assert!(filled.contains(&(4, 2)));
+        assert!(!filled.contains(&(2, 2)));
+
+        let filled = flood_fill(&map, (0, 0), Connectivity::Full, |_, &c| c == '#');
+        assert_eq!(filled, HashSet::from([(0, 0), (1, 0), (0, 1)]));
+
+        let filled = flood_fill(&map, (0, 0), Connectivity::Orthogonal, |_, &c| c == '.');
+        assert!(filled.is_empty());
+    }
+
+    #[test]
+    fn test_label_components() {
+        let map = create_map();
+
+        let (labels, components) =
+            label_components(&map, Connectivity::Orthogonal, |_, &c| c == '#');
+
+        assert_eq!(components.len(), 5);
+        assert_eq!(labels.get((0, 0)), &Some(0));
+        assert_eq!(labels.get((0, 1)), &Some(0));
+        assert_eq!(labels.get((4, 1)), &Some(1));
+        assert_eq!(labels.get((2, 2)), &Some(2));
+        assert_eq!(labels.get((3, 4)), &Some(3));
+        assert_eq!(labels.get((0, 4)), &Some(4));
+        assert_eq!(labels.get((1, 1)), &None);
+        assert_eq!(
+            components[0],
+            Component {
+                size: 3,
+                bounds: ((0, 0), (1, 1))
+            }
+        );
+        assert_eq!(
+            components[3],
+            Component {
+                size: 4,
+                bounds: ((3, 3), (4, 4))
+            }
+        );
+
+        let (_, components) = label_components(&map, Connectivity::Full, |_, &c| c == '#');
+
+        assert_eq!(components.len(), 4);
+    }
+
+    #[test]
+    fn test_flood_fill_points() {
+        let walls: HashSet<(isize, isize)> = HashSet::from([(1, 0), (1, 1), (0, 1)]);
+
+        let filled = flood_fill_points((0, 0), Connectivity::Orthogonal, |point| {
+            !walls.contains(point) && point.0 >= -1 && point.1 >= -1 && point.0 <= 2 && point.1 <= 2
+        });
+
+        assert_eq!(filled.len(), 16 - 3);
+
+        let inside = flood_fill_points((0, 0), Connectivity::Orthogonal, |point| {
+            !walls.contains(point) && point.0 >= 0 && point.1 >= 0 && point.0 <= 2 && point.1 <= 2
+        });
+
+        assert_eq!(inside, HashSet::from([(0, 0)]));
+    }
+
+    #[test]
+    fn test_label_points() {
+        let points: HashMap<(isize, isize), char> =
+            HashMap::from([((0, 0), 'a'), ((1, 0), 'b'), ((5, 5), 'c'), ((6, 6), 'd')]);
+
+        let (labels, components) = label_points(points.keys().copied(), Connectivity::Orthogonal);
+
+        assert_eq!(components.len(), 3);
+        assert_eq!(labels[&(0, 0)], labels[&(1, 0)]);
+        assert_ne!(labels[&(5, 5)], labels[&(6, 6)]);
+
+        let (_, components) = label_points(points.keys().copied(), Connectivity::Full);
+        let mut sizes: Vec<usize> = components.iter().map(|c| c.size).collect();
+        sizes.sort();
+
+        assert_eq!(sizes, vec![2, 2]);
+    }
+
+    #[test]
+    fn test_label_voxels() {
+        let voxels = vec![(0, 0, 0), (0, 0, 1), (1, 1, 2), (5, 5, 5)];
+
+        let (_, components) = label_points(voxels.clone(), Connectivity::Orthogonal);
+        assert_eq!(components.len(), 3);
+        assert_eq!(
+            components[0],
+            Component {
+                size: 2,
+                bounds: ((0, 0, 0), (0, 0, 1))
+            }
+        );
+
+        let (_, components) = label_points(voxels, Connectivity::Full);
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[0].size, 3);
+        assert_eq!(components[0].bounds, ((0, 0, 0), (1, 1, 2)));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        assert_eq!((0, 0).neighbors(Connectivity::Orthogonal).len(), 4);
+        assert_eq!((0, 0).neighbors(Connectivity::Full).len(), 8);
+        assert_eq!((0, 0, 0).neighbors(Connectivity::Orthogonal).len(), 6);
+        assert_eq!((0, 0, 0).neighbors(Connectivity::Full).len(), 26);
+    }
+}
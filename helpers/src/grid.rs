@@ -1,39 +1,107 @@
+use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
-use std::iter::FromIterator;
+use std::hash::Hash;
+use std::ops::{Index, IndexMut};
+
+use crate::Direction;
 
 pub type Coord = (usize, usize);
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Topology {
+    #[default]
+    Bounded,
+    WrapX,
+    WrapY,
+    Torus,
+}
+
+impl Topology {
+    pub fn wraps_x(&self) -> bool {
+        matches!(self, Topology::WrapX | Topology::Torus)
+    }
+
+    pub fn wraps_y(&self) -> bool {
+        matches!(self, Topology::WrapY | Topology::Torus)
+    }
+
+    /// Topology with the axes swapped, as after a transpose or a quarter rotation.
+    pub fn transposed(&self) -> Self {
+        match self {
+            Topology::WrapX => Topology::WrapY,
+            Topology::WrapY => Topology::WrapX,
+            topology => *topology,
+        }
+    }
+
+    /// Maps a coordinate into a grid of `size`, wrapping the axes this topology wraps.
+    pub fn resolve(&self, size: (usize, usize), coord: (isize, isize)) -> Option<Coord> {
+        let x = Self::resolve_axis(coord.0, size.0, self.wraps_x())?;
+        let y = Self::resolve_axis(coord.1, size.1, self.wraps_y())?;
+
+        Some((x, y))
+    }
+
+    fn resolve_axis(value: isize, length: usize, wraps: bool) -> Option<usize> {
+        if wraps && length > 0 {
+            Some(value.rem_euclid(length as isize) as usize)
+        } else if value < 0 || value >= length as isize {
+            None
+        } else {
+            Some(value as usize)
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct Grid<T> {
     pub size: (usize, usize),
     cells: Vec<T>,
+    topology: Topology,
 }
 
 impl<T> Grid<T> {
     pub fn new(size: (usize, usize), cells: Vec<T>) -> Self {
         assert_eq!(size.0 * size.1, cells.len());
 
-        Grid { size, cells }
+        Grid {
+            size,
+            cells,
+            topology: Topology::Bounded,
+        }
+    }
+
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    pub fn set_topology(&mut self, topology: Topology) {
+        self.topology = topology;
     }
 
     pub fn len(&self) -> usize {
         self.size.0 * self.size.1
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
     pub fn get(&self, coord: Coord) -> &T {
         &self.cells[self.index(coord)]
     }
 
+    /// Outside coordinates are wrapped or rejected depending on the topology.
     pub fn maybe_get(&self, coord: (isize, isize)) -> Option<&T> {
-        if coord.0 < 0
-            || coord.1 < 0
-            || coord.0 >= self.size.0 as isize
-            || coord.1 >= self.size.1 as isize
-        {
-            return None;
-        }
+        let coord = self.topology.resolve(self.size, coord)?;
 
-        Some(&self.cells[self.index((coord.0 as usize, coord.1 as usize))])
+        Some(&self.cells[self.index(coord)])
     }
 
     pub fn get_mut(&mut self, coord: Coord) -> &mut T {
@@ -46,7 +114,69 @@ impl<T> Grid<T> {
         self.cells[index] = value;
     }
 
-    pub fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = (Coord, &T)> + 'a> {
+    pub fn try_get(&self, coord: Coord) -> Option<&T> {
+        self.contains(coord).then(|| &self.cells[self.index(coord)])
+    }
+
+    pub fn try_get_mut(&mut self, coord: Coord) -> Option<&mut T> {
+        if !self.contains(coord) {
+            return None;
+        }
+
+        let index = self.index(coord);
+        Some(&mut self.cells[index])
+    }
+
+    pub fn contains(&self, coord: Coord) -> bool {
+        coord.0 < self.size.0 && coord.1 < self.size.1
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.size.1);
+
+        &self.cells[y * self.size.0..(y + 1) * self.size.0]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        assert!(y < self.size.1);
+
+        &mut self.cells[y * self.size.0..(y + 1) * self.size.0]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.size.1).map(|y| self.row(y))
+    }
+
+    /// Cells of column `x` from top to bottom.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(x < self.size.0);
+
+        self.cells.iter().skip(x).step_by(self.size.0)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.size.0).map(|x| self.column(x))
+    }
+
+    /// Builds a grid of the same size and topology from each cell.
+    pub fn map<U, F>(&self, mut f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        self.map_with_coord(|_, cell| f(cell))
+    }
+
+    /// Builds a grid of the same size and topology from each cell and its coordinate.
+    pub fn map_with_coord<U, F>(&self, mut f: F) -> Grid<U>
+    where
+        F: FnMut(Coord, &T) -> U,
+    {
+        let cells = self.iter().map(|(coord, cell)| f(coord, cell)).collect();
+
+        Grid::new(self.size, cells).with_topology(self.topology)
+    }
+
+    pub fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = (Coord, &'a T)> + 'a> {
         Box::new(
             self.cells
                 .iter()
@@ -66,11 +196,143 @@ impl<T> Grid<T> {
         )
     }
 
-    pub fn neighbors_iter(&self, coord: Coord, with_diagonals: bool) -> NeighborIter<T> {
+    pub fn neighbors_iter(&self, coord: Coord, with_diagonals: bool) -> NeighborIter<'_, T> {
+        assert!(coord.0 < self.size.0);
+        assert!(coord.1 < self.size.1);
+
+        NeighborIter::new(self, coord, with_diagonals)
+    }
+
+    pub fn neighbors_iter_with<'a>(
+        &'a self,
+        coord: Coord,
+        neighborhood: Neighborhood<'a>,
+    ) -> NeighborIter<'a, T> {
+        assert!(coord.0 < self.size.0);
+        assert!(coord.1 < self.size.1);
+
+        NeighborIter::with_neighborhood(self, coord, neighborhood)
+    }
+
+    pub fn step(&self, coord: Coord, direction: Direction) -> Option<Coord> {
+        let offset = direction.offset();
+
+        self.topology.resolve(
+            self.size,
+            (coord.0 as isize + offset.0, coord.1 as isize + offset.1),
+        )
+    }
+
+    /// Cells from `coord` (excluded) towards `direction` until the edge, or until the ray
+    /// wraps back to `coord` on a wrapping topology.
+    pub fn ray(&self, coord: Coord, direction: Direction) -> RayIter<'_, T> {
         assert!(coord.0 < self.size.0);
         assert!(coord.1 < self.size.1);
 
-        NeighborIter::new(&self, coord, with_diagonals)
+        RayIter {
+            grid: self,
+            origin: coord,
+            coord,
+            direction,
+        }
+    }
+
+    /// Borrows the rectangle starting at `origin`, its coordinates are relative to `origin`.
+    pub fn view(&self, origin: Coord, size: (usize, usize)) -> GridView<'_, T> {
+        assert!(origin.0 + size.0 <= self.size.0);
+        assert!(origin.1 + size.1 <= self.size.1);
+
+        GridView {
+            grid: self,
+            origin,
+            size,
+        }
+    }
+
+    pub fn push_row(&mut self, row: Vec<T>) {
+        self.insert_row(self.size.1, row);
+    }
+
+    pub fn push_column(&mut self, column: Vec<T>) {
+        self.insert_column(self.size.0, column);
+    }
+
+    /// Inserts `row` before row `y`, moving the following rows down.
+    pub fn insert_row(&mut self, y: usize, row: Vec<T>) {
+        assert!(y <= self.size.1);
+
+        let (width, height) = if self.is_empty() {
+            (row.len(), 0)
+        } else {
+            self.size
+        };
+        assert_eq!(row.len(), width);
+
+        let index = y.min(height) * width;
+        self.cells.splice(index..index, row);
+        self.size = (width, height + 1);
+    }
+
+    /// Inserts `column` before column `x`, moving the following columns right.
+    pub fn insert_column(&mut self, x: usize, column: Vec<T>) {
+        assert!(x <= self.size.0);
+
+        let (width, height) = if self.is_empty() {
+            (0, column.len())
+        } else {
+            self.size
+        };
+        assert_eq!(column.len(), height);
+
+        let mut old = std::mem::take(&mut self.cells).into_iter();
+        let mut cells = Vec::with_capacity((width + 1) * height);
+
+        for value in column {
+            cells.extend(old.by_ref().take(x.min(width)));
+            cells.push(value);
+            cells.extend(old.by_ref().take(width - x.min(width)));
+        }
+
+        self.cells = cells;
+        self.size = (width + 1, height);
+    }
+
+    /// First cell matching `predicate` in row-major order.
+    pub fn find<P>(&self, predicate: P) -> Option<Coord>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|index| self.coord(index))
+    }
+
+    pub fn positions<'a, P>(&'a self, mut predicate: P) -> impl Iterator<Item = Coord> + 'a
+    where
+        P: FnMut(&T) -> bool + 'a,
+    {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(move |(_, cell)| predicate(cell))
+            .map(|(index, _)| self.coord(index))
+    }
+
+    pub fn count<P>(&self, mut predicate: P) -> usize
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.cells.iter().filter(|cell| predicate(cell)).count()
+    }
+
+    #[cfg(feature = "parallel")]
+    pub(crate) fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub(crate) fn cells_mut(&mut self) -> &mut [T] {
+        &mut self.cells
     }
 
     fn index(&self, coord: Coord) -> usize {
@@ -89,6 +351,160 @@ impl<T> Grid<T> {
     }
 }
 
+impl<T: Clone> Grid<T> {
+    /// Grows or shrinks to `size`, keeping cells at their coordinates and filling new ones.
+    pub fn resize(&mut self, size: (usize, usize), fill: T) {
+        let old_width = self.size.0;
+        let mut old = std::mem::take(&mut self.cells).into_iter();
+        let mut cells = Vec::with_capacity(size.0 * size.1);
+
+        for y in 0..size.1 {
+            let row: Vec<T> = old
+                .by_ref()
+                .take(if y < self.size.1 { old_width } else { 0 })
+                .collect();
+            cells.extend(row.into_iter().take(size.0));
+
+            let missing = size.0 * (y + 1) - cells.len();
+            cells.extend(std::iter::repeat_n(fill.clone(), missing));
+        }
+
+        self.cells = cells;
+        self.size = size;
+    }
+
+    /// Adds cells around the grid and returns the offset to add to previous coordinates.
+    pub fn pad(&mut self, left: usize, top: usize, right: usize, bottom: usize, fill: T) -> Coord {
+        let (width, height) = self.size;
+        let new_width = left + width + right;
+        let mut old = std::mem::take(&mut self.cells).into_iter();
+        let mut cells = Vec::with_capacity(new_width * (top + height + bottom));
+
+        cells.extend(std::iter::repeat_n(fill.clone(), new_width * top));
+
+        for _ in 0..height {
+            cells.extend(std::iter::repeat_n(fill.clone(), left));
+            cells.extend(old.by_ref().take(width));
+            cells.extend(std::iter::repeat_n(fill.clone(), right));
+        }
+
+        cells.extend(std::iter::repeat_n(fill, new_width * bottom));
+
+        self.cells = cells;
+        self.size = (new_width, top + height + bottom);
+
+        (left, top)
+    }
+
+    /// Sets every cell matching `predicate` to `value`, returns how many were replaced.
+    pub fn replace<P>(&mut self, mut predicate: P, value: T) -> usize
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let mut replaced = 0;
+
+        for cell in self.cells.iter_mut().filter(|cell| predicate(cell)) {
+            *cell = value.clone();
+            replaced += 1;
+        }
+
+        replaced
+    }
+
+    /// Rotates clockwise.
+    pub fn rotate_90(&self) -> Grid<T> {
+        let height = self.size.1;
+
+        self.remap((self.size.1, self.size.0), |(x, y)| (y, height - 1 - x))
+            .with_topology(self.topology.transposed())
+    }
+
+    pub fn rotate_180(&self) -> Grid<T> {
+        let size = self.size;
+
+        self.remap(size, |(x, y)| (size.0 - 1 - x, size.1 - 1 - y))
+            .with_topology(self.topology)
+    }
+
+    /// Rotates counterclockwise.
+    pub fn rotate_270(&self) -> Grid<T> {
+        let width = self.size.0;
+
+        self.remap((self.size.1, self.size.0), |(x, y)| (width - 1 - y, x))
+            .with_topology(self.topology.transposed())
+    }
+
+    pub fn transpose(&self) -> Grid<T> {
+        self.remap((self.size.1, self.size.0), |(x, y)| (y, x))
+            .with_topology(self.topology.transposed())
+    }
+
+    /// Mirrors left to right.
+    pub fn flip_horizontal(&self) -> Grid<T> {
+        let width = self.size.0;
+
+        self.remap(self.size, |(x, y)| (width - 1 - x, y))
+            .with_topology(self.topology)
+    }
+
+    /// Mirrors top to bottom.
+    pub fn flip_vertical(&self) -> Grid<T> {
+        let height = self.size.1;
+
+        self.remap(self.size, |(x, y)| (x, height - 1 - y))
+            .with_topology(self.topology)
+    }
+
+    /// Builds a grid of `size` where each cell is taken from `source(coord)` in this grid.
+    fn remap<F>(&self, size: (usize, usize), source: F) -> Grid<T>
+    where
+        F: Fn(Coord) -> Coord,
+    {
+        let cells = (0..size.0 * size.1)
+            .map(|index| self.get(source(Self::_coord(size, index))).clone())
+            .collect();
+
+        Grid::new(size, cells)
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    pub fn position_of(&self, value: &T) -> Option<Coord> {
+        self.find(|cell| cell == value)
+    }
+
+    pub fn find_all(&self, value: &T) -> Vec<Coord> {
+        self.positions(|cell| cell == value).collect()
+    }
+}
+
+impl<T: Eq + Hash + Clone> Grid<T> {
+    /// Coordinates of each distinct value in row-major order, collected in a single pass.
+    pub fn value_index(&self) -> HashMap<T, Vec<Coord>> {
+        let mut index: HashMap<T, Vec<Coord>> = HashMap::new();
+
+        for (coord, cell) in self.iter() {
+            index.entry(cell.clone()).or_default().push(coord);
+        }
+
+        index
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coord: Coord) -> &T {
+        self.get(coord)
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, coord: Coord) -> &mut T {
+        self.get_mut(coord)
+    }
+}
+
 impl<T: Debug> Debug for Grid<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         writeln!(f)?;
@@ -105,126 +521,277 @@ impl<T: Debug> Debug for Grid<T> {
     }
 }
 
+/// Grids are equal when they have the same size and cells, whatever their topology.
 impl<T: PartialEq> PartialEq for Grid<T> {
     fn eq(&self, other: &Self) -> bool {
-        self.cells == other.cells
+        self.size == other.size && self.cells == other.cells
     }
 }
 
-impl<T> FromIterator<(Coord, T)> for Grid<T> {
-    fn from_iter<K: IntoIterator<Item = (Coord, T)>>(iter: K) -> Self {
-        let mut cells = Vec::new();
-        let mut size = (0, 0);
+impl<T: Eq> Eq for Grid<T> {}
 
-        for (coord, value) in iter.into_iter() {
-            cells.push(value);
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    origin: Coord,
+    pub size: (usize, usize),
+}
 
-            if coord.0 > size.0 {
-                size.0 = coord.0;
-            }
+impl<'a, T> GridView<'a, T> {
+    pub fn len(&self) -> usize {
+        self.size.0 * self.size.1
+    }
 
-            if coord.1 > size.1 {
-                size.1 = coord.1;
-            }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, coord: Coord) -> &'a T {
+        assert!(coord.0 < self.size.0);
+        assert!(coord.1 < self.size.1);
+
+        self.grid
+            .get((self.origin.0 + coord.0, self.origin.1 + coord.1))
+    }
+
+    pub fn maybe_get(&self, coord: (isize, isize)) -> Option<&'a T> {
+        if coord.0 < 0
+            || coord.1 < 0
+            || coord.0 >= self.size.0 as isize
+            || coord.1 >= self.size.1 as isize
+        {
+            return None;
         }
 
-        Grid::new((size.0 + 1, size.1 + 1), cells)
+        Some(self.get((coord.0 as usize, coord.1 as usize)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &'a T)> + '_ {
+        (0..self.len()).map(move |index| {
+            let coord = Grid::<T>::_coord(self.size, index);
+
+            (coord, self.get(coord))
+        })
+    }
+
+    /// Absolute coordinate in the underlying grid.
+    pub fn to_grid_coord(&self, coord: Coord) -> Coord {
+        (self.origin.0 + coord.0, self.origin.1 + coord.1)
+    }
+}
+
+impl<'a, T: Clone> GridView<'a, T> {
+    pub fn to_grid(&self) -> Grid<T> {
+        Grid::new(
+            self.size,
+            self.iter().map(|(_, cell)| cell.clone()).collect(),
+        )
     }
 }
 
-struct NeighborCoordIter<'a, T> {
+pub struct RayIter<'a, T> {
     grid: &'a Grid<T>,
+    origin: Coord,
     coord: Coord,
-    visited: Vec<usize>,
-    with_diagonals: bool,
+    direction: Direction,
 }
 
-impl<'a, T> NeighborCoordIter<'a, T> {
-    pub fn new(grid: &'a Grid<T>, coord: Coord, with_diagonals: bool) -> Self {
+impl<'a, T> Iterator for RayIter<'a, T> {
+    type Item = (Coord, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let grid = self.grid;
+        let coord = grid
+            .step(self.coord, self.direction)
+            .filter(|&coord| coord != self.origin)?;
+        self.coord = coord;
+
+        Some((coord, grid.get(coord)))
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Neighborhood<'a> {
+    /// Left, top, right and bottom.
+    VonNeumann,
+    /// Clockwise from left, diagonals included.
+    Moore,
+    Knight,
+    /// Every cell within the Chebyshev `radius`, in row-major order.
+    Radius(usize),
+    Offsets(&'a [(isize, isize)]),
+}
+
+const VON_NEUMANN_OFFSETS: [(isize, isize); 4] = [(-1, 0), (0, -1), (1, 0), (0, 1)];
+
+const MOORE_OFFSETS: [(isize, isize); 8] = [
+    (-1, 0),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+];
+
+const KNIGHT_OFFSETS: [(isize, isize); 8] = [
+    (-2, -1),
+    (-1, -2),
+    (1, -2),
+    (2, -1),
+    (2, 1),
+    (1, 2),
+    (-1, 2),
+    (-2, 1),
+];
+
+impl<'a> Neighborhood<'a> {
+    pub fn len(&self) -> usize {
+        match self {
+            Neighborhood::VonNeumann => VON_NEUMANN_OFFSETS.len(),
+            Neighborhood::Moore => MOORE_OFFSETS.len(),
+            Neighborhood::Knight => KNIGHT_OFFSETS.len(),
+            Neighborhood::Radius(radius) => (2 * radius + 1).pow(2) - 1,
+            Neighborhood::Offsets(offsets) => offsets.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn offset(&self, index: usize) -> (isize, isize) {
+        match self {
+            Neighborhood::VonNeumann => VON_NEUMANN_OFFSETS[index],
+            Neighborhood::Moore => MOORE_OFFSETS[index],
+            Neighborhood::Knight => KNIGHT_OFFSETS[index],
+            Neighborhood::Radius(radius) => {
+                assert!(index < self.len());
+
+                let side = 2 * radius + 1;
+                // Skip the center cell
+                let index = if index >= side * side / 2 {
+                    index + 1
+                } else {
+                    index
+                };
+
+                (
+                    (index % side) as isize - *radius as isize,
+                    (index / side) as isize - *radius as isize,
+                )
+            }
+            Neighborhood::Offsets(offsets) => offsets[index],
+        }
+    }
+
+    /// Largest offset along either axis.
+    fn reach(&self) -> usize {
+        match self {
+            Neighborhood::VonNeumann | Neighborhood::Moore => 1,
+            Neighborhood::Knight => 2,
+            Neighborhood::Radius(radius) => *radius,
+            Neighborhood::Offsets(offsets) => offsets
+                .iter()
+                .map(|offset| offset.0.unsigned_abs().max(offset.1.unsigned_abs()))
+                .max()
+                .unwrap_or(0),
+        }
+    }
+}
+
+struct NeighborCoordIter<'a> {
+    size: (usize, usize),
+    topology: Topology,
+    coord: Coord,
+    neighborhood: Neighborhood<'a>,
+    index: usize,
+    // Only tracked when a wrapped axis is short enough for offsets to land on the same cell
+    yielded: Option<Vec<Coord>>,
+}
+
+impl<'a> NeighborCoordIter<'a> {
+    fn new(
+        size: (usize, usize),
+        topology: Topology,
+        coord: Coord,
+        neighborhood: Neighborhood<'a>,
+    ) -> Self {
+        let span = 2 * neighborhood.reach();
+        let aliases =
+            (topology.wraps_x() && size.0 <= span) || (topology.wraps_y() && size.1 <= span);
+
         NeighborCoordIter {
-            grid,
+            size,
+            topology,
             coord,
-            visited: vec![],
-            with_diagonals,
+            neighborhood,
+            index: 0,
+            yielded: aliases.then(Vec::new),
         }
     }
 }
 
-impl<'a, T> Iterator for NeighborCoordIter<'a, T> {
+impl<'a> Iterator for NeighborCoordIter<'a> {
     type Item = Coord;
 
     fn next(&mut self) -> Option<Self::Item> {
-        // Left
-        if !self.visited.contains(&0) && self.coord.0 != 0 {
-            self.visited.push(0);
-            Some((self.coord.0 - 1, self.coord.1))
-        } else
-        // Left-top
-        if self.with_diagonals
-            && !self.visited.contains(&1)
-            && self.coord.0 != 0
-            && self.coord.1 != 0
-        {
-            self.visited.push(1);
-            Some((self.coord.0 - 1, self.coord.1 - 1))
-        } else
-        // Top
-        if !self.visited.contains(&2) && self.coord.1 != 0 {
-            self.visited.push(2);
-            Some((self.coord.0, self.coord.1 - 1))
-        } else
-        // Top-right
-        if self.with_diagonals
-            && !self.visited.contains(&3)
-            && self.coord.0 + 1 != self.grid.size.0
-            && self.coord.1 != 0
-        {
-            self.visited.push(3);
-            Some((self.coord.0 + 1, self.coord.1 - 1))
-        } else
-        // Right
-        if !self.visited.contains(&4) && self.coord.0 + 1 != self.grid.size.0 {
-            self.visited.push(4);
-            Some((self.coord.0 + 1, self.coord.1))
-        } else
-        // Bottom-right
-        if self.with_diagonals
-            && !self.visited.contains(&5)
-            && self.coord.0 + 1 != self.grid.size.0
-            && self.coord.1 + 1 != self.grid.size.1
-        {
-            self.visited.push(5);
-            Some((self.coord.0 + 1, self.coord.1 + 1))
-        } else
-        // Bottom
-        if !self.visited.contains(&6) && self.coord.1 + 1 != self.grid.size.1 {
-            self.visited.push(6);
-            Some((self.coord.0, self.coord.1 + 1))
-        } else
-        // Bottom-left
-        if self.with_diagonals
-            && !self.visited.contains(&7)
-            && self.coord.0 != 0
-            && self.coord.1 + 1 != self.grid.size.1
-        {
-            self.visited.push(7);
-            Some((self.coord.0 - 1, self.coord.1 + 1))
-        } else {
-            None
+        while self.index < self.neighborhood.len() {
+            let offset = self.neighborhood.offset(self.index);
+            self.index += 1;
+
+            let neighbor = self.topology.resolve(
+                self.size,
+                (
+                    self.coord.0 as isize + offset.0,
+                    self.coord.1 as isize + offset.1,
+                ),
+            );
+
+            let Some(neighbor) = neighbor else {
+                continue;
+            };
+
+            if let Some(yielded) = &mut self.yielded {
+                // On a short wrapped axis, offsets can land back on the cell or repeat a neighbor
+                if (neighbor == self.coord && offset != (0, 0)) || yielded.contains(&neighbor) {
+                    continue;
+                }
+
+                yielded.push(neighbor);
+            }
+
+            return Some(neighbor);
         }
+
+        None
     }
 }
 
 pub struct NeighborIter<'a, T> {
     grid: &'a Grid<T>,
-    coord_iter: NeighborCoordIter<'a, T>,
+    coord_iter: NeighborCoordIter<'a>,
 }
 
 impl<'a, T> NeighborIter<'a, T> {
     pub fn new(grid: &'a Grid<T>, coord: Coord, with_diagonals: bool) -> Self {
+        let neighborhood = if with_diagonals {
+            Neighborhood::Moore
+        } else {
+            Neighborhood::VonNeumann
+        };
+
+        Self::with_neighborhood(grid, coord, neighborhood)
+    }
+
+    pub fn with_neighborhood(
+        grid: &'a Grid<T>,
+        coord: Coord,
+        neighborhood: Neighborhood<'a>,
+    ) -> Self {
         NeighborIter {
             grid,
-            coord_iter: NeighborCoordIter::new(grid, coord, with_diagonals),
+            coord_iter: NeighborCoordIter::new(grid.size, grid.topology, coord, neighborhood),
         }
     }
 }
@@ -241,6 +808,8 @@ impl<'a, T> Iterator for NeighborIter<'a, T> {
 
 #[cfg(test)]
 mod tests {
+    use crate::input_grid;
+
     use super::*;
 
     fn create_grid() -> Grid<char> {
@@ -409,4 +978,623 @@ mod tests {
             [((2, 4), &'s'), ((2, 3), &'o'), ((3, 3), &'p')].to_vec()
         );
     }
+
+    #[test]
+    fn test_step() {
+        let grid = create_grid();
+
+        assert_eq!(grid.step((1, 2), Direction::Left), Some((0, 2)));
+        assert_eq!(grid.step((1, 2), Direction::BottomRight), Some((2, 3)));
+        assert_eq!(grid.step((0, 2), Direction::Left), None);
+        assert_eq!(grid.step((3, 4), Direction::Bottom), None);
+        assert_eq!(grid.step((3, 4), Direction::Right), None);
+    }
+
+    #[test]
+    fn test_ray() {
+        let grid = create_grid();
+
+        assert_eq!(
+            grid.ray((1, 2), Direction::Left)
+                .collect::<Vec<(Coord, &char)>>(),
+            [((0, 2), &'i')].to_vec()
+        );
+        assert_eq!(
+            grid.ray((1, 2), Direction::Bottom)
+                .collect::<Vec<(Coord, &char)>>(),
+            [((1, 3), &'n'), ((1, 4), &'r')].to_vec()
+        );
+        assert_eq!(
+            grid.ray((0, 0), Direction::BottomRight)
+                .collect::<Vec<(Coord, &char)>>(),
+            [((1, 1), &'f'), ((2, 2), &'k'), ((3, 3), &'p')].to_vec()
+        );
+        assert_eq!(grid.ray((3, 0), Direction::TopRight).next(), None);
+    }
+
+    #[test]
+    fn test_rotate_90() {
+        let grid = create_grid().rotate_90();
+
+        assert_eq!(grid.size, (5, 4));
+        assert_eq!(
+            grid,
+            Grid::new(
+                (5, 4),
+                [
+                    'q', 'm', 'i', 'e', 'a', //
+                    'r', 'n', 'j', 'f', 'b', //
+                    's', 'o', 'k', 'g', 'c', //
+                    't', 'p', 'l', 'h', 'd', //
+                ]
+                .to_vec(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_rotate_180() {
+        let grid = create_grid().rotate_180();
+
+        assert_eq!(grid.size, (4, 5));
+        assert_eq!(
+            grid,
+            Grid::new(
+                (4, 5),
+                [
+                    't', 's', 'r', 'q', //
+                    'p', 'o', 'n', 'm', //
+                    'l', 'k', 'j', 'i', //
+                    'h', 'g', 'f', 'e', //
+                    'd', 'c', 'b', 'a', //
+                ]
+                .to_vec(),
+            )
+        );
+        assert_eq!(create_grid().rotate_90().rotate_90(), grid);
+    }
+
+    #[test]
+    fn test_rotate_270() {
+        let grid = create_grid().rotate_270();
+
+        assert_eq!(grid.size, (5, 4));
+        assert_eq!(
+            grid,
+            Grid::new(
+                (5, 4),
+                [
+                    'd', 'h', 'l', 'p', 't', //
+                    'c', 'g', 'k', 'o', 's', //
+                    'b', 'f', 'j', 'n', 'r', //
+                    'a', 'e', 'i', 'm', 'q', //
+                ]
+                .to_vec(),
+            )
+        );
+        assert_eq!(grid.rotate_90(), create_grid());
+    }
+
+    #[test]
+    fn test_transpose() {
+        let grid = create_grid().transpose();
+
+        assert_eq!(grid.size, (5, 4));
+        assert_eq!(
+            grid,
+            Grid::new(
+                (5, 4),
+                [
+                    'a', 'e', 'i', 'm', 'q', //
+                    'b', 'f', 'j', 'n', 'r', //
+                    'c', 'g', 'k', 'o', 's', //
+                    'd', 'h', 'l', 'p', 't', //
+                ]
+                .to_vec(),
+            )
+        );
+        assert_eq!(grid.transpose(), create_grid());
+    }
+
+    #[test]
+    fn test_flip_horizontal() {
+        let grid = create_grid().flip_horizontal();
+
+        assert_eq!(grid.size, (4, 5));
+        assert_eq!(
+            grid,
+            Grid::new(
+                (4, 5),
+                [
+                    'd', 'c', 'b', 'a', //
+                    'h', 'g', 'f', 'e', //
+                    'l', 'k', 'j', 'i', //
+                    'p', 'o', 'n', 'm', //
+                    't', 's', 'r', 'q', //
+                ]
+                .to_vec(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_flip_vertical() {
+        let grid = create_grid().flip_vertical();
+
+        assert_eq!(grid.size, (4, 5));
+        assert_eq!(
+            grid,
+            Grid::new(
+                (4, 5),
+                [
+                    'q', 'r', 's', 't', //
+                    'm', 'n', 'o', 'p', //
+                    'i', 'j', 'k', 'l', //
+                    'e', 'f', 'g', 'h', //
+                    'a', 'b', 'c', 'd', //
+                ]
+                .to_vec(),
+            )
+        );
+        assert_eq!(grid.flip_horizontal(), create_grid().rotate_180());
+    }
+
+    #[test]
+    fn test_view() {
+        let grid = create_grid();
+        let view = grid.view((1, 2), (2, 3));
+
+        assert_eq!(view.size, (2, 3));
+        assert_eq!(view.len(), 6);
+        assert_eq!(view.get((0, 0)), &'j');
+        assert_eq!(view.get((1, 2)), &'s');
+        assert_eq!(view.maybe_get((2, 0)), None);
+        assert_eq!(view.maybe_get((-1, 0)), None);
+        assert_eq!(view.maybe_get((1, 1)), Some(&'o'));
+        assert_eq!(view.to_grid_coord((1, 1)), (2, 3));
+        assert_eq!(
+            view.iter().collect::<Vec<(Coord, &char)>>(),
+            [
+                ((0, 0), &'j'),
+                ((1, 0), &'k'),
+                ((0, 1), &'n'),
+                ((1, 1), &'o'),
+                ((0, 2), &'r'),
+                ((1, 2), &'s')
+            ]
+            .to_vec()
+        );
+        assert_eq!(
+            view.to_grid(),
+            Grid::new((2, 3), ['j', 'k', 'n', 'o', 'r', 's'].to_vec())
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_view_out_of_bounds() {
+        create_grid().view((3, 0), (2, 1));
+    }
+
+    #[test]
+    fn test_iter_cell_neighbors_with_knight() {
+        let grid = create_grid();
+
+        assert_eq!(
+            grid.neighbors_iter_with((1, 2), Neighborhood::Knight)
+                .collect::<Vec<(Coord, &char)>>(),
+            [
+                ((0, 0), &'a'),
+                ((2, 0), &'c'),
+                ((3, 1), &'h'),
+                ((3, 3), &'p'),
+                ((2, 4), &'s'),
+                ((0, 4), &'q')
+            ]
+            .to_vec()
+        );
+    }
+
+    #[test]
+    fn test_iter_cell_neighbors_with_radius() {
+        let grid = create_grid();
+
+        assert_eq!(
+            grid.neighbors_iter_with((0, 0), Neighborhood::Radius(2))
+                .map(|(_, &cell)| cell)
+                .collect::<String>(),
+            "bcefgijk"
+        );
+        assert_eq!(
+            grid.neighbors_iter_with((2, 2), Neighborhood::Radius(2))
+                .count(),
+            19
+        );
+        assert_eq!(
+            grid.neighbors_iter_with((1, 1), Neighborhood::Radius(1))
+                .map(|(_, &cell)| cell)
+                .collect::<String>(),
+            "abcegijk"
+        );
+    }
+
+    #[test]
+    fn test_iter_cell_neighbors_with_offsets() {
+        let grid = create_grid();
+
+        assert_eq!(
+            grid.neighbors_iter_with((1, 1), Neighborhood::Offsets(&[(0, 3), (-2, 0), (2, 2)]))
+                .collect::<Vec<(Coord, &char)>>(),
+            [((1, 4), &'r'), ((3, 3), &'p')].to_vec()
+        );
+    }
+
+    #[test]
+    fn test_neighborhood_len() {
+        assert_eq!(Neighborhood::VonNeumann.len(), 4);
+        assert_eq!(Neighborhood::Moore.len(), 8);
+        assert_eq!(Neighborhood::Knight.len(), 8);
+        assert_eq!(Neighborhood::Radius(1).len(), 8);
+        assert_eq!(Neighborhood::Radius(3).len(), 48);
+        assert_eq!(Neighborhood::Offsets(&[]).len(), 0);
+    }
+
+    #[test]
+    fn test_topology_maybe_get() {
+        let grid = create_grid();
+        assert_eq!(grid.maybe_get((-1, 0)), None);
+
+        let grid = create_grid().with_topology(Topology::WrapX);
+        assert_eq!(grid.maybe_get((-1, 0)), Some(&'d'));
+        assert_eq!(grid.maybe_get((9, 1)), Some(&'f'));
+        assert_eq!(grid.maybe_get((0, 5)), None);
+
+        let grid = create_grid().with_topology(Topology::WrapY);
+        assert_eq!(grid.maybe_get((-1, 0)), None);
+        assert_eq!(grid.maybe_get((0, -1)), Some(&'q'));
+
+        let grid = create_grid().with_topology(Topology::Torus);
+        assert_eq!(grid.maybe_get((-1, -1)), Some(&'t'));
+        assert_eq!(grid.maybe_get((4, 5)), Some(&'a'));
+    }
+
+    #[test]
+    fn test_topology_neighbors() {
+        let grid = create_grid().with_topology(Topology::Torus);
+
+        assert_eq!(
+            grid.neighbors_iter((0, 0), false)
+                .collect::<Vec<(Coord, &char)>>(),
+            [
+                ((3, 0), &'d'),
+                ((0, 4), &'q'),
+                ((1, 0), &'b'),
+                ((0, 1), &'e')
+            ]
+            .to_vec()
+        );
+        assert_eq!(grid.neighbors_iter((3, 4), true).count(), 8);
+
+        let grid = create_grid().with_topology(Topology::WrapY);
+
+        assert_eq!(
+            grid.neighbors_iter((0, 0), false)
+                .collect::<Vec<(Coord, &char)>>(),
+            [((0, 4), &'q'), ((1, 0), &'b'), ((0, 1), &'e')].to_vec()
+        );
+    }
+
+    #[test]
+    fn test_topology_neighbors_small_torus() {
+        let grid = Grid::new((2, 2), vec!['a', 'b', 'c', 'd']).with_topology(Topology::Torus);
+
+        assert_eq!(
+            grid.neighbors_iter((0, 0), false)
+                .collect::<Vec<(Coord, &char)>>(),
+            [((1, 0), &'b'), ((0, 1), &'c')].to_vec()
+        );
+        assert_eq!(
+            grid.neighbors_iter((0, 0), true)
+                .collect::<Vec<(Coord, &char)>>(),
+            [((1, 0), &'b'), ((1, 1), &'d'), ((0, 1), &'c')].to_vec()
+        );
+
+        // A wrapped axis of length 1 never makes the cell its own neighbor
+        let grid = Grid::new((1, 3), vec!['a', 'b', 'c']).with_topology(Topology::WrapX);
+
+        assert_eq!(
+            grid.neighbors_iter((0, 1), true)
+                .collect::<Vec<(Coord, &char)>>(),
+            [((0, 0), &'a'), ((0, 2), &'c')].to_vec()
+        );
+    }
+
+    #[test]
+    fn test_topology_step() {
+        let grid = create_grid().with_topology(Topology::WrapX);
+
+        assert_eq!(grid.step((0, 2), Direction::Left), Some((3, 2)));
+        assert_eq!(grid.step((3, 2), Direction::TopRight), Some((0, 1)));
+        assert_eq!(grid.step((3, 4), Direction::Bottom), None);
+    }
+
+    #[test]
+    fn test_topology_ray() {
+        let grid = create_grid().with_topology(Topology::WrapX);
+
+        assert_eq!(
+            grid.ray((1, 2), Direction::Right)
+                .map(|(_, &cell)| cell)
+                .collect::<String>(),
+            "kli"
+        );
+        assert_eq!(
+            grid.ray((1, 2), Direction::Bottom)
+                .map(|(_, &cell)| cell)
+                .collect::<String>(),
+            "nr"
+        );
+
+        let grid = create_grid().with_topology(Topology::Torus);
+
+        assert_eq!(grid.ray((0, 0), Direction::BottomRight).count(), 19);
+    }
+
+    #[test]
+    fn test_topology_transforms() {
+        let grid = create_grid().with_topology(Topology::WrapX);
+
+        assert_eq!(grid.rotate_90().topology(), Topology::WrapY);
+        assert_eq!(grid.transpose().topology(), Topology::WrapY);
+        assert_eq!(grid.rotate_180().topology(), Topology::WrapX);
+        assert_eq!(grid.flip_vertical().topology(), Topology::WrapX);
+    }
+
+    #[test]
+    fn test_push_row() {
+        let mut grid = create_grid();
+        grid.push_row(['u', 'v', 'w', 'x'].to_vec());
+
+        assert_eq!(grid.size, (4, 6));
+        assert_eq!(grid.get((0, 5)), &'u');
+        assert_eq!(grid.get((3, 5)), &'x');
+        assert_eq!(grid.get((3, 4)), &'t');
+
+        let mut grid = Grid::new((0, 0), Vec::new());
+        grid.push_row([1, 2, 3].to_vec());
+
+        assert_eq!(grid.size, (3, 1));
+    }
+
+    #[test]
+    fn test_push_column() {
+        let mut grid = create_grid();
+        grid.push_column(['1', '2', '3', '4', '5'].to_vec());
+
+        assert_eq!(grid.size, (5, 5));
+        assert_eq!(grid.get((4, 0)), &'1');
+        assert_eq!(grid.get((0, 1)), &'e');
+        assert_eq!(grid.get((4, 4)), &'5');
+        assert_eq!(grid.get((3, 4)), &'t');
+
+        let mut grid = Grid::new((0, 0), Vec::new());
+        grid.push_column([1, 2].to_vec());
+
+        assert_eq!(grid.size, (1, 2));
+
+        let mut grid = Grid::new((3, 0), Vec::new());
+        grid.push_column([1, 2].to_vec());
+
+        assert_eq!(grid.size, (1, 2));
+    }
+
+    #[test]
+    fn test_insert_row() {
+        let mut grid = create_grid();
+        grid.insert_row(1, ['1', '2', '3', '4'].to_vec());
+
+        assert_eq!(grid.size, (4, 6));
+        assert_eq!(grid.get((0, 0)), &'a');
+        assert_eq!(grid.get((0, 1)), &'1');
+        assert_eq!(grid.get((0, 2)), &'e');
+        assert_eq!(grid.get((3, 5)), &'t');
+    }
+
+    #[test]
+    fn test_insert_column() {
+        let mut grid = create_grid();
+        grid.insert_column(0, ['1', '2', '3', '4', '5'].to_vec());
+
+        assert_eq!(grid.size, (5, 5));
+        assert_eq!(
+            grid,
+            Grid::new(
+                (5, 5),
+                [
+                    '1', 'a', 'b', 'c', 'd', //
+                    '2', 'e', 'f', 'g', 'h', //
+                    '3', 'i', 'j', 'k', 'l', //
+                    '4', 'm', 'n', 'o', 'p', //
+                    '5', 'q', 'r', 's', 't', //
+                ]
+                .to_vec(),
+            )
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_insert_column_with_wrong_length() {
+        create_grid().insert_column(0, ['1'].to_vec());
+    }
+
+    #[test]
+    fn test_resize() {
+        let mut grid = create_grid();
+        grid.resize((5, 2), '.');
+
+        assert_eq!(grid.size, (5, 2));
+        assert_eq!(
+            grid,
+            Grid::new(
+                (5, 2),
+                [
+                    'a', 'b', 'c', 'd', '.', //
+                    'e', 'f', 'g', 'h', '.', //
+                ]
+                .to_vec(),
+            )
+        );
+
+        let mut grid = create_grid();
+        grid.resize((2, 6), '.');
+
+        assert_eq!(
+            grid,
+            Grid::new(
+                (2, 6),
+                [
+                    'a', 'b', //
+                    'e', 'f', //
+                    'i', 'j', //
+                    'm', 'n', //
+                    'q', 'r', //
+                    '.', '.', //
+                ]
+                .to_vec(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_pad() {
+        let mut grid = Grid::new((2, 2), ['a', 'b', 'c', 'd'].to_vec());
+        let offset = grid.pad(1, 2, 0, 1, '.');
+
+        assert_eq!(offset, (1, 2));
+        assert_eq!(grid.size, (3, 5));
+        assert_eq!(
+            grid,
+            Grid::new(
+                (3, 5),
+                [
+                    '.', '.', '.', //
+                    '.', '.', '.', //
+                    '.', 'a', 'b', //
+                    '.', 'c', 'd', //
+                    '.', '.', '.', //
+                ]
+                .to_vec(),
+            )
+        );
+        assert_eq!(grid.get((1 + offset.0, 1 + offset.1)), &'d');
+    }
+
+    #[test]
+    fn test_find() {
+        let grid = input_grid("abca\ncbab");
+
+        assert_eq!(grid.find(|&c| c == 'c'), Some((2, 0)));
+        assert_eq!(grid.find(|&c| c == 'z'), None);
+        assert_eq!(grid.position_of(&'b'), Some((1, 0)));
+        assert_eq!(grid.find_all(&'a'), vec![(0, 0), (3, 0), (2, 1)]);
+        assert_eq!(
+            grid.positions(|&c| c != 'a').collect::<Vec<Coord>>(),
+            vec![(1, 0), (2, 0), (0, 1), (1, 1), (3, 1)]
+        );
+        assert_eq!(grid.count(|&c| c == 'b'), 3);
+    }
+
+    #[test]
+    fn test_replace() {
+        let mut grid = input_grid("abca\ncbab");
+
+        assert_eq!(grid.replace(|&c| c == 'a', '.'), 3);
+        assert_eq!(grid, input_grid(".bc.\ncb.b"));
+    }
+
+    #[test]
+    fn test_value_index() {
+        let index = input_grid::<char>("S..\n.#E").value_index();
+
+        assert_eq!(index[&'S'], vec![(0, 0)]);
+        assert_eq!(index[&'E'], vec![(2, 1)]);
+        assert_eq!(index[&'.'], vec![(1, 0), (2, 0), (0, 1)]);
+        assert_eq!(index.len(), 4);
+    }
+
+    #[test]
+    fn test_index() {
+        let mut grid = create_grid();
+        assert_eq!(grid[(2, 3)], 'o');
+
+        grid[(2, 3)] = 'x';
+        assert_eq!(grid[(2, 3)], 'x');
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_out_of_bounds() {
+        let _ = create_grid()[(4, 0)];
+    }
+
+    #[test]
+    fn test_try_get() {
+        let mut grid = create_grid();
+
+        assert_eq!(grid.try_get((3, 4)), Some(&'t'));
+        assert_eq!(grid.try_get((4, 0)), None);
+        assert_eq!(grid.try_get((0, 5)), None);
+
+        *grid.try_get_mut((0, 0)).unwrap() = 'x';
+        assert_eq!(grid[(0, 0)], 'x');
+        assert!(grid.try_get_mut((0, 5)).is_none());
+    }
+
+    #[test]
+    fn test_rows() {
+        let mut grid = create_grid();
+
+        assert_eq!(grid.row(1), &['e', 'f', 'g', 'h']);
+        assert_eq!(grid.rows().count(), 5);
+        assert_eq!(grid.rows().last(), Some(&['q', 'r', 's', 't'][..]));
+
+        grid.row_mut(0).reverse();
+        assert_eq!(grid.row(0), &['d', 'c', 'b', 'a']);
+    }
+
+    #[test]
+    fn test_columns() {
+        let grid = create_grid();
+
+        assert_eq!(grid.column(1).collect::<String>(), "bfjnr".to_string());
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.collect::<String>())
+                .collect::<Vec<String>>(),
+            vec!["aeimq", "bfjnr", "cgkos", "dhlpt"]
+        );
+    }
+
+    #[test]
+    fn test_map() {
+        let grid = create_grid().with_topology(Topology::WrapX);
+        let upper = grid.map(|c| c.to_ascii_uppercase());
+
+        assert_eq!(upper[(2, 3)], 'O');
+        assert_eq!(upper.topology(), Topology::WrapX);
+
+        let sums = grid.map_with_coord(|(x, y), _| x + y);
+        assert_eq!(sums.size, (4, 5));
+        assert_eq!(sums[(3, 4)], 7);
+    }
+
+    #[test]
+    fn test_eq_compares_size() {
+        let wide = Grid::new((3, 2), vec![1, 2, 3, 4, 5, 6]);
+        let tall = Grid::new((2, 3), vec![1, 2, 3, 4, 5, 6]);
+
+        assert_ne!(wide, tall);
+        assert_eq!(wide, wide.clone().with_topology(Topology::Torus));
+    }
 }
@@ -0,0 +1,388 @@
+use std::ops::{Add, Sub};
+use std::str::FromStr;
+
+use crate::{Coord, Grid};
+
+/// Hexagon in axial coordinates, the third cube coordinate is `s = -q - r`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Hex {
+    pub q: isize,
+    pub r: isize,
+}
+
+/// Neighbour directions of pointy-top hexagons, counterclockwise from east.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HexDirection {
+    East,
+    NorthEast,
+    NorthWest,
+    West,
+    SouthWest,
+    SouthEast,
+}
+
+/// How hexagons are stored in a rectangular `Grid`: rows (pointy-top) or columns (flat-top),
+/// with odd or even rows/columns shoved by half a cell.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OffsetLayout {
+    OddR,
+    EvenR,
+    OddQ,
+    EvenQ,
+}
+
+impl Hex {
+    pub fn new(q: isize, r: isize) -> Self {
+        Hex { q, r }
+    }
+
+    pub fn s(&self) -> isize {
+        -self.q - self.r
+    }
+
+    pub fn neighbor(&self, direction: HexDirection) -> Hex {
+        *self + direction.offset()
+    }
+
+    pub fn neighbors(&self) -> [Hex; 6] {
+        HexDirection::ALL.map(|direction| self.neighbor(direction))
+    }
+
+    pub fn distance(&self, other: &Hex) -> usize {
+        let difference = *self - *other;
+
+        (difference.q.unsigned_abs() + difference.r.unsigned_abs() + difference.s().unsigned_abs())
+            / 2
+    }
+
+    /// Hexagons at exactly `radius` steps, starting at the south-west corner and going
+    /// counterclockwise.
+    pub fn ring(&self, radius: usize) -> impl Iterator<Item = Hex> {
+        let start = Hex::new(
+            self.q + HexDirection::SouthWest.offset().q * radius as isize,
+            self.r + HexDirection::SouthWest.offset().r * radius as isize,
+        );
+        let length = if radius == 0 { 1 } else { 6 * radius };
+
+        (0..length).scan(start, move |hex, step| {
+            let current = *hex;
+
+            if let Some(side) = step.checked_div(radius) {
+                *hex = hex.neighbor(HexDirection::ALL[side]);
+            }
+
+            Some(current)
+        })
+    }
+
+    /// Hexagons on the straight line to `other`, both ends included.
+    pub fn line(&self, other: &Hex) -> impl Iterator<Item = Hex> {
+        let distance = self.distance(other);
+        let (start, end) = (*self, *other);
+
+        (0..=distance).map(move |step| {
+            if distance == 0 {
+                return start;
+            }
+
+            let t = step as f64 / distance as f64;
+            // Nudge off the edges so ties between two hexagons round consistently
+            let lerp =
+                |a: isize, b: isize, nudge: f64| a as f64 + 1e-6 * nudge + (b - a) as f64 * t;
+
+            Hex::round(
+                lerp(start.q, end.q, 1.0),
+                lerp(start.r, end.r, 1.0),
+                lerp(start.s(), end.s(), -2.0),
+            )
+        })
+    }
+
+    pub fn to_offset(&self, layout: OffsetLayout) -> (isize, isize) {
+        match layout {
+            OffsetLayout::OddR => (self.q + (self.r - (self.r & 1)) / 2, self.r),
+            OffsetLayout::EvenR => (self.q + (self.r + (self.r & 1)) / 2, self.r),
+            OffsetLayout::OddQ => (self.q, self.r + (self.q - (self.q & 1)) / 2),
+            OffsetLayout::EvenQ => (self.q, self.r + (self.q + (self.q & 1)) / 2),
+        }
+    }
+
+    pub fn from_offset(offset: (isize, isize), layout: OffsetLayout) -> Hex {
+        let (x, y) = offset;
+
+        match layout {
+            OffsetLayout::OddR => Hex::new(x - (y - (y & 1)) / 2, y),
+            OffsetLayout::EvenR => Hex::new(x - (y + (y & 1)) / 2, y),
+            OffsetLayout::OddQ => Hex::new(x, y - (x - (x & 1)) / 2),
+            OffsetLayout::EvenQ => Hex::new(x, y - (x + (x & 1)) / 2),
+        }
+    }
+
+    fn round(q: f64, r: f64, s: f64) -> Hex {
+        let (mut rounded_q, mut rounded_r, rounded_s) = (q.round(), r.round(), s.round());
+        let (diff_q, diff_r, diff_s) = (
+            (rounded_q - q).abs(),
+            (rounded_r - r).abs(),
+            (rounded_s - s).abs(),
+        );
+
+        if diff_q > diff_r && diff_q > diff_s {
+            rounded_q = -rounded_r - rounded_s;
+        } else if diff_r > diff_s {
+            rounded_r = -rounded_q - rounded_s;
+        }
+
+        Hex::new(rounded_q as isize, rounded_r as isize)
+    }
+}
+
+impl Add for Hex {
+    type Output = Hex;
+
+    fn add(self, other: Hex) -> Hex {
+        Hex::new(self.q + other.q, self.r + other.r)
+    }
+}
+
+impl Sub for Hex {
+    type Output = Hex;
+
+    fn sub(self, other: Hex) -> Hex {
+        Hex::new(self.q - other.q, self.r - other.r)
+    }
+}
+
+impl HexDirection {
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::East,
+        HexDirection::NorthEast,
+        HexDirection::NorthWest,
+        HexDirection::West,
+        HexDirection::SouthWest,
+        HexDirection::SouthEast,
+    ];
+
+    pub fn offset(&self) -> Hex {
+        match self {
+            HexDirection::East => Hex::new(1, 0),
+            HexDirection::NorthEast => Hex::new(1, -1),
+            HexDirection::NorthWest => Hex::new(0, -1),
+            HexDirection::West => Hex::new(-1, 0),
+            HexDirection::SouthWest => Hex::new(-1, 1),
+            HexDirection::SouthEast => Hex::new(0, 1),
+        }
+    }
+
+    pub fn opposite(&self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|direction| direction == self)
+            .unwrap();
+
+        Self::ALL[(index + 3) % 6]
+    }
+}
+
+impl FromStr for HexDirection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "e" => Ok(HexDirection::East),
+            "ne" => Ok(HexDirection::NorthEast),
+            "nw" => Ok(HexDirection::NorthWest),
+            "w" => Ok(HexDirection::West),
+            "sw" => Ok(HexDirection::SouthWest),
+            "se" => Ok(HexDirection::SouthEast),
+            _ => Err(format!("Unknown hex direction: {}", s)),
+        }
+    }
+}
+
+impl OffsetLayout {
+    /// Coordinate of `hex` in a grid of `size` stored with this layout.
+    pub fn coord(&self, hex: &Hex, size: (usize, usize)) -> Option<Coord> {
+        let (x, y) = hex.to_offset(*self);
+
+        if x < 0 || y < 0 || x >= size.0 as isize || y >= size.1 as isize {
+            return None;
+        }
+
+        Some((x as usize, y as usize))
+    }
+
+    fn is_shoved(&self, index: usize) -> bool {
+        match self {
+            OffsetLayout::OddR | OffsetLayout::OddQ => !index.is_multiple_of(2),
+            OffsetLayout::EvenR | OffsetLayout::EvenQ => index.is_multiple_of(2),
+        }
+    }
+}
+
+/// Draws a grid holding hexagons in `layout` as staggered text.
+pub fn render_hex<T, F>(grid: &Grid<T>, layout: OffsetLayout, cell: F) -> String
+where
+    F: Fn(&T) -> char,
+{
+    let (width, height) = grid.size;
+
+    let lines: Vec<String> = match layout {
+        OffsetLayout::OddR | OffsetLayout::EvenR => (0..height)
+            .map(|y| {
+                let indent = if layout.is_shoved(y) { " " } else { "" };
+                let cells: Vec<String> = (0..width)
+                    .map(|x| cell(grid.get((x, y))).to_string())
+                    .collect();
+
+                indent.to_string() + &cells.join(" ")
+            })
+            .collect(),
+        OffsetLayout::OddQ | OffsetLayout::EvenQ => {
+            let mut canvas = vec![vec![' '; (width * 2).saturating_sub(1)]; height * 2 + 1];
+
+            for ((x, y), value) in grid.iter() {
+                let row = y * 2 + usize::from(layout.is_shoved(x));
+                canvas[row][x * 2] = cell(value);
+            }
+
+            canvas
+                .into_iter()
+                .map(|line| line.into_iter().collect())
+                .collect()
+        }
+    };
+
+    let mut lines: Vec<&str> = lines.iter().map(|line| line.trim_end()).collect();
+
+    while lines.last() == Some(&"") {
+        lines.pop();
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_neighbors() {
+        let hex = Hex::new(2, -1);
+
+        assert!(hex
+            .neighbors()
+            .iter()
+            .all(|neighbor| hex.distance(neighbor) == 1));
+        assert_eq!(hex.neighbor(HexDirection::East), Hex::new(3, -1));
+        assert_eq!(hex.neighbor(HexDirection::SouthWest), Hex::new(1, 0));
+        assert_eq!(
+            hex.neighbor(HexDirection::NorthEast)
+                .neighbor(HexDirection::NorthEast.opposite()),
+            hex
+        );
+    }
+
+    #[test]
+    fn test_distance() {
+        assert_eq!(Hex::new(0, 0).distance(&Hex::new(0, 0)), 0);
+        assert_eq!(Hex::new(0, 0).distance(&Hex::new(3, -1)), 3);
+        assert_eq!(Hex::new(-2, 1).distance(&Hex::new(2, 2)), 5);
+    }
+
+    #[test]
+    fn test_parse_direction() {
+        let path: Vec<HexDirection> = ["ne", "ne", "sw", "se"]
+            .iter()
+            .map(|direction| direction.parse().unwrap())
+            .collect();
+        let end = path
+            .iter()
+            .fold(Hex::default(), |hex, &direction| hex.neighbor(direction));
+
+        assert_eq!(end, Hex::new(1, 0));
+        assert!("n".parse::<HexDirection>().is_err());
+    }
+
+    #[test]
+    fn test_ring() {
+        let center = Hex::new(1, 1);
+
+        assert_eq!(center.ring(0).collect::<Vec<Hex>>(), vec![center]);
+
+        let ring: Vec<Hex> = center.ring(2).collect();
+        assert_eq!(ring.len(), 12);
+        assert!(ring.iter().all(|hex| center.distance(hex) == 2));
+        assert!(ring.windows(2).all(|pair| pair[0].distance(&pair[1]) == 1));
+        assert_eq!(ring[0].distance(&ring[11]), 1);
+    }
+
+    #[test]
+    fn test_line() {
+        let line: Vec<Hex> = Hex::new(0, 0).line(&Hex::new(3, -3)).collect();
+
+        assert_eq!(
+            line,
+            vec![
+                Hex::new(0, 0),
+                Hex::new(1, -1),
+                Hex::new(2, -2),
+                Hex::new(3, -3)
+            ]
+        );
+
+        let line: Vec<Hex> = Hex::new(-2, 0).line(&Hex::new(2, 1)).collect();
+        assert_eq!(line.len(), 6);
+        assert!(line.windows(2).all(|pair| pair[0].distance(&pair[1]) == 1));
+        assert_eq!(Hex::new(1, 1).line(&Hex::new(1, 1)).count(), 1);
+    }
+
+    #[test]
+    fn test_offset_round_trip() {
+        for layout in [
+            OffsetLayout::OddR,
+            OffsetLayout::EvenR,
+            OffsetLayout::OddQ,
+            OffsetLayout::EvenQ,
+        ] {
+            for q in -3..=3 {
+                for r in -3..=3 {
+                    let hex = Hex::new(q, r);
+                    assert_eq!(Hex::from_offset(hex.to_offset(layout), layout), hex);
+                }
+            }
+        }
+
+        assert_eq!(Hex::new(-1, 3).to_offset(OffsetLayout::OddR), (0, 3));
+        assert_eq!(Hex::new(-2, 3).to_offset(OffsetLayout::EvenR), (0, 3));
+    }
+
+    #[test]
+    fn test_grid_storage() {
+        let mut grid = Grid::new((3, 3), vec!['.'; 9]);
+        let center = Hex::from_offset((1, 1), OffsetLayout::OddR);
+
+        for neighbor in center.neighbors() {
+            let coord = OffsetLayout::OddR.coord(&neighbor, grid.size).unwrap();
+            grid.set(coord, '#');
+        }
+
+        assert_eq!(
+            render_hex(&grid, OffsetLayout::OddR, |&c| c),
+            ". # #\n # . #\n. # #"
+        );
+        assert_eq!(OffsetLayout::OddR.coord(&Hex::new(-1, 0), grid.size), None);
+    }
+
+    #[test]
+    fn test_render_columns() {
+        let grid = Grid::new((3, 2), vec!['a', 'b', 'c', 'd', 'e', 'f']);
+
+        assert_eq!(
+            render_hex(&grid, OffsetLayout::OddQ, |&c| c),
+            "a   c\n  b\nd   f\n  e"
+        );
+    }
+}
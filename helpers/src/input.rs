@@ -11,7 +11,7 @@ where
     input
         .lines()
         .map(|line| line.trim())
-        .filter(|line| false == line.is_empty())
+        .filter(|line| !line.is_empty())
         .collect()
 }
 pub fn input_lines_raw<'a, R>(input: &'a str) -> R
@@ -28,7 +28,7 @@ where
     input
         .split(pattern)
         .map(|line| line.trim())
-        .filter(|line| false == line.is_empty())
+        .filter(|line| !line.is_empty())
         .collect()
 }
 
@@ -40,7 +40,7 @@ where
     let lines: Vec<&str> = input
         .lines()
         .map(|line| line.trim())
-        .filter(|line| false == line.is_empty())
+        .filter(|line| !line.is_empty())
         .collect();
 
     let size = (lines[0].len(), lines.len());
@@ -63,7 +63,7 @@ where
     input
         .split(pattern)
         .map(|line| line.trim())
-        .filter(|line| false == line.is_empty())
+        .filter(|line| !line.is_empty())
         .map(|line| line.parse().unwrap())
         .collect()
 }
@@ -76,7 +76,7 @@ where
     input
         .lines()
         .map(|line| line.trim())
-        .filter(|line| false == line.is_empty())
+        .filter(|line| !line.is_empty())
         .map(|line| line.parse().unwrap())
         .collect()
 }
@@ -139,9 +139,7 @@ mod tests {
             "            2-9 c: ccccccccc",
             "        ",
         ]
-        .iter()
-        .cloned()
-        .collect();
+        .to_vec();
         assert_eq!(result, expected);
     }
 
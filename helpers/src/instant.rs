@@ -1,6 +1,7 @@
 use std::time::{Duration, Instant};
 
 pub trait BasicInstant {
+    #[allow(dead_code)]
     fn now() -> Self;
 
     fn elapsed(&self) -> Duration;
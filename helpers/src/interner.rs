@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/// Unsigned integer types usable as interned ids.
+pub trait Id: Copy + Eq + Hash + Debug {
+    /// Panics if `index` does not fit.
+    fn from_index(index: usize) -> Self;
+    fn index(self) -> usize;
+}
+
+macro_rules! impl_id {
+    ($($t:ty),*) => {
+        $(
+            impl Id for $t {
+                fn from_index(index: usize) -> Self {
+                    <$t>::try_from(index).expect("Too many labels for the id type")
+                }
+
+                fn index(self) -> usize {
+                    self as usize
+                }
+            }
+        )*
+    };
+}
+
+impl_id!(u8, u16, u32, usize);
+
+/// Maps labels to dense ids, handed out from 0 in order of first appearance, and back.
+#[derive(Debug, Clone)]
+pub struct Interner<I = u32> {
+    ids: HashMap<String, I>,
+    labels: Vec<String>,
+}
+
+impl<I: Id> Interner<I> {
+    pub fn new() -> Self {
+        Interner {
+            ids: HashMap::new(),
+            labels: Vec::new(),
+        }
+    }
+
+    /// Id of `label`, assigning the next one if it is new.
+    pub fn intern(&mut self, label: &str) -> I {
+        if let Some(&id) = self.ids.get(label) {
+            return id;
+        }
+
+        let id = I::from_index(self.labels.len());
+        self.ids.insert(label.to_string(), id);
+        self.labels.push(label.to_string());
+
+        id
+    }
+
+    pub fn get(&self, label: &str) -> Option<I> {
+        self.ids.get(label).copied()
+    }
+
+    /// Panics if `id` was not handed out by this interner.
+    pub fn resolve(&self, id: I) -> &str {
+        &self.labels[id.index()]
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Ids and labels in id order.
+    pub fn iter(&self) -> impl Iterator<Item = (I, &str)> + '_ {
+        self.labels
+            .iter()
+            .enumerate()
+            .map(|(index, label)| (I::from_index(index), label.as_str()))
+    }
+}
+
+impl<I: Id> Default for Interner<I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intern() {
+        let mut interner: Interner<u16> = Interner::new();
+
+        assert!(interner.is_empty());
+        assert_eq!(interner.intern("AA"), 0);
+        assert_eq!(interner.intern("BB"), 1);
+        assert_eq!(interner.intern("AA"), 0);
+        assert_eq!(interner.len(), 2);
+
+        assert_eq!(interner.get("BB"), Some(1));
+        assert_eq!(interner.get("CC"), None);
+        assert_eq!(interner.resolve(1), "BB");
+        assert_eq!(
+            interner.iter().collect::<Vec<(u16, &str)>>(),
+            vec![(0, "AA"), (1, "BB")]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Too many labels for the id type")]
+    fn test_id_overflow() {
+        let mut interner: Interner<u8> = Interner::new();
+
+        for index in 0..=256 {
+            interner.intern(&index.to_string());
+        }
+    }
+}
@@ -0,0 +1,331 @@
+use std::ops::{Bound, Range, RangeBounds, RangeInclusive};
+
+pub use crate::math::Integer;
+
+/// Set of integers stored as sorted, disjoint and non-adjacent half-open intervals.
+///
+/// Methods taking a range accept both half-open (`a..b`) and inclusive (`a..=b`) ones.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Range<T>>,
+}
+
+impl<T: Integer> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet {
+            intervals: Vec::new(),
+        }
+    }
+
+    /// Adds `range`, merging it with the intervals it overlaps or touches.
+    pub fn insert<R: RangeBounds<T>>(&mut self, range: R) {
+        let Range { start, end } = Self::half_open(range);
+        if start >= end {
+            return;
+        }
+
+        let first = self
+            .intervals
+            .partition_point(|interval| interval.end < start);
+        let last = self
+            .intervals
+            .partition_point(|interval| interval.start <= end);
+
+        if first == last {
+            self.intervals.insert(first, start..end);
+            return;
+        }
+
+        let start = start.min(self.intervals[first].start);
+        let end = end.max(self.intervals[last - 1].end);
+
+        self.intervals[first] = start..end;
+        self.intervals.drain(first + 1..last);
+    }
+
+    pub fn remove<R: RangeBounds<T>>(&mut self, range: R) {
+        let Range { start, end } = Self::half_open(range);
+        if start >= end {
+            return;
+        }
+
+        let first = self
+            .intervals
+            .partition_point(|interval| interval.end <= start);
+        let last = self
+            .intervals
+            .partition_point(|interval| interval.start < end);
+        if first >= last {
+            return;
+        }
+
+        let left = self.intervals[first].start..start;
+        let right = end..self.intervals[last - 1].end;
+        let kept = [left, right].into_iter().filter(|part| !part.is_empty());
+
+        self.intervals.splice(first..last, kept);
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let index = self.first_ending_after(value);
+
+        index < self.intervals.len() && self.intervals[index].start <= value
+    }
+
+    /// Whether every value of `range` is in the set.
+    pub fn contains_range<R: RangeBounds<T>>(&self, range: R) -> bool {
+        let range = Self::half_open(range);
+        if range.is_empty() {
+            return true;
+        }
+
+        // The only interval that can hold the range is the one holding its start
+        let index = self.first_ending_after(range.start);
+
+        index < self.intervals.len()
+            && self.intervals[index].start <= range.start
+            && range.end <= self.intervals[index].end
+    }
+
+    /// Whether every value of `other` is in the set.
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.iter().all(|interval| self.contains_range(interval))
+    }
+
+    /// Whether any value of `range` is in the set.
+    pub fn overlaps<R: RangeBounds<T>>(&self, range: R) -> bool {
+        let range = Self::half_open(range);
+        if range.is_empty() {
+            return false;
+        }
+
+        let index = self.first_ending_after(range.start);
+
+        index < self.intervals.len() && self.intervals[index].start < range.end
+    }
+
+    /// Number of values in the set.
+    pub fn len(&self) -> T {
+        self.intervals.iter().fold(T::ZERO, |len, interval| {
+            len + (interval.end - interval.start)
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for interval in other.iter() {
+            union.insert(interval);
+        }
+
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let (mut a, mut b) = (0, 0);
+
+        while a < self.intervals.len() && b < other.intervals.len() {
+            let (left, right) = (&self.intervals[a], &other.intervals[b]);
+            let overlap = left.start.max(right.start)..left.end.min(right.end);
+
+            if !overlap.is_empty() {
+                intervals.push(overlap);
+            }
+
+            if left.end < right.end {
+                a += 1;
+            } else {
+                b += 1;
+            }
+        }
+
+        IntervalSet { intervals }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        for interval in other.iter() {
+            difference.remove(interval);
+        }
+
+        difference
+    }
+
+    /// Values of `bounds` missing from the set.
+    pub fn gaps_within<R: RangeBounds<T>>(&self, bounds: R) -> Self {
+        let mut gaps = IntervalSet::new();
+        gaps.insert(bounds);
+
+        gaps.difference(self)
+    }
+
+    /// Intervals in ascending order as half-open ranges.
+    pub fn iter(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.intervals.iter().cloned()
+    }
+
+    /// Intervals in ascending order as inclusive ranges.
+    pub fn iter_inclusive(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.intervals
+            .iter()
+            .map(|interval| interval.start..=interval.end - T::ONE)
+    }
+
+    fn half_open<R: RangeBounds<T>>(range: R) -> Range<T> {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start + T::ONE,
+            Bound::Unbounded => panic!("Intervals must have a start"),
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end + T::ONE,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => panic!("Intervals must have an end"),
+        };
+
+        start..end
+    }
+
+    /// Index of the first interval ending after `value`, i.e. the one that would hold it.
+    fn first_ending_after(&self, value: T) -> usize {
+        self.intervals
+            .partition_point(|interval| interval.end <= value)
+    }
+}
+
+impl<T: Integer> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Integer, R: RangeBounds<T>> Extend<R> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = R>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl<T: Integer, R: RangeBounds<T>> FromIterator<R> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = R>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        set.extend(iter);
+
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter::once;
+
+    use super::*;
+
+    fn ranges(set: &IntervalSet<isize>) -> Vec<Range<isize>> {
+        set.iter().collect()
+    }
+
+    #[test]
+    fn test_insert_merges() {
+        let mut set = IntervalSet::new();
+        set.insert(10..15);
+        set.insert(0..3);
+        set.insert(5..=7);
+        assert_eq!(ranges(&set), vec![0..3, 5..8, 10..15]);
+
+        // Touching intervals merge, the gap at 4 does not
+        set.insert(3..4);
+        assert_eq!(ranges(&set), vec![0..4, 5..8, 10..15]);
+
+        set.insert(6..12);
+        assert_eq!(ranges(&set), vec![0..4, 5..15]);
+
+        set.insert(2..2);
+        assert_eq!(ranges(&set), vec![0..4, 5..15]);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut set: IntervalSet<isize> = [0..10, 20..30].into_iter().collect();
+
+        set.remove(5..=24);
+        assert_eq!(ranges(&set), vec![0..5, 25..30]);
+
+        set.remove(0..5);
+        assert_eq!(ranges(&set), vec![25..30]);
+
+        set.remove(40..50);
+        assert_eq!(ranges(&set), vec![25..30]);
+    }
+
+    #[test]
+    fn test_contains() {
+        let set: IntervalSet<isize> = [-5..=-2, 3..=3].into_iter().collect();
+
+        assert!(set.contains(-5));
+        assert!(set.contains(-2));
+        assert!(!set.contains(-1));
+        assert!(set.contains(3));
+        assert!(!set.contains(4));
+
+        assert!(set.contains_range(-4..=-2));
+        assert!(!set.contains_range(-4..=3));
+        assert!(set.contains_range(3..=3));
+        assert!(set.contains_range(10..10));
+        assert!(!set.contains_range(-1..=0));
+        assert!(set.overlaps(-1..=3));
+        assert!(set.overlaps(-10..=-5));
+        assert!(!set.overlaps(-1..3));
+        assert!(!set.overlaps(-10..-5));
+        assert!(!set.overlaps(4..10));
+        assert!(!set.overlaps(-3..-3));
+    }
+
+    #[test]
+    fn test_is_superset() {
+        let set: IntervalSet<isize> = [0..10, 20..30].into_iter().collect();
+
+        assert!(set.is_superset(&[2..5, 20..21].into_iter().collect()));
+        assert!(set.is_superset(&set));
+        assert!(set.is_superset(&IntervalSet::new()));
+        assert!(!set.is_superset(&once(5..15).collect()));
+        assert!(!IntervalSet::new().is_superset(&set));
+    }
+
+    #[test]
+    fn test_len() {
+        let set: IntervalSet<usize> = [2..=4, 6..=8, 3..=5].into_iter().collect();
+
+        assert_eq!(set.len(), 7);
+        assert!(!set.is_empty());
+        assert!(IntervalSet::<usize>::new().is_empty());
+        assert_eq!(set.iter_inclusive().collect::<Vec<_>>(), vec![2..=8]);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a: IntervalSet<isize> = [0..10, 20..30].into_iter().collect();
+        let b: IntervalSet<isize> = [5..25, 28..40].into_iter().collect();
+
+        assert_eq!(ranges(&a.union(&b)), vec![0..40]);
+        assert_eq!(ranges(&a.intersection(&b)), vec![5..10, 20..25, 28..30]);
+        assert_eq!(ranges(&a.difference(&b)), vec![0..5, 25..28]);
+        assert_eq!(ranges(&b.difference(&a)), vec![10..20, 30..40]);
+    }
+
+    #[test]
+    fn test_gaps_within() {
+        let set: IntervalSet<isize> = [-2..=2, 5..=8, 12..=20].into_iter().collect();
+
+        assert_eq!(
+            set.gaps_within(0..=14).iter_inclusive().collect::<Vec<_>>(),
+            vec![3..=4, 9..=11]
+        );
+        assert!(set.gaps_within(13..15).is_empty());
+    }
+}
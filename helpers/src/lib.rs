@@ -1,8 +1,35 @@
+pub use bits::*;
+pub use builder::*;
+pub use direction::*;
 pub use grid::*;
 pub use input::*;
+#[cfg(feature = "parallel")]
+pub use rayon;
+pub use render::*;
 pub use run::*;
 
+pub mod automaton;
+mod bits;
+mod builder;
+pub mod cycle;
+mod direction;
+pub mod fill;
 mod grid;
+pub mod hex;
 mod input;
 mod instant;
+pub mod interner;
+pub mod intervals;
+pub mod lines;
+pub mod math;
+pub mod memo;
+pub mod ocr;
+#[cfg(feature = "parallel")]
+mod parallel;
+mod render;
+pub mod ring;
 mod run;
+pub mod search;
+#[cfg(feature = "serde")]
+pub mod serialize;
+pub mod state_search;
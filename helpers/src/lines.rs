@@ -0,0 +1,187 @@
+use std::collections::HashSet;
+
+use crate::Grid;
+
+pub type Point = (isize, isize);
+
+/// Points of the segment from `from` to `to`, both included. Axis-aligned and diagonal
+/// segments are exact, other slopes follow Bresenham's algorithm.
+pub fn line(from: Point, to: Point) -> LineIter {
+    let delta = ((to.0 - from.0).abs(), -(to.1 - from.1).abs());
+
+    LineIter {
+        current: Some(from),
+        to,
+        delta,
+        step: ((to.0 - from.0).signum(), (to.1 - from.1).signum()),
+        error: delta.0 + delta.1,
+    }
+}
+
+/// Points of the segments joining consecutive `vertices`, shared vertices appear once.
+pub fn polyline<I>(vertices: I) -> impl Iterator<Item = Point>
+where
+    I: IntoIterator<Item = Point>,
+{
+    let vertices: Vec<Point> = vertices.into_iter().collect();
+    // A single vertex is drawn as a segment to itself
+    let segments = match vertices.len() {
+        0 => 0,
+        1 => 1,
+        len => len - 1,
+    };
+
+    (0..segments).flat_map(move |index| {
+        let to = vertices.get(index + 1).unwrap_or(&vertices[index]);
+
+        line(vertices[index], *to).skip(if index == 0 { 0 } else { 1 })
+    })
+}
+
+/// Smallest `(min, max)` corners containing every point.
+pub fn bounds<I>(points: I) -> Option<(Point, Point)>
+where
+    I: IntoIterator<Item = Point>,
+{
+    points.into_iter().fold(None, |bounds, point| match bounds {
+        None => Some((point, point)),
+        Some((min, max)) => Some((
+            (min.0.min(point.0), min.1.min(point.1)),
+            (max.0.max(point.0), max.1.max(point.1)),
+        )),
+    })
+}
+
+/// Sets every point to `value` and returns their bounds. Points must lie in the grid.
+pub fn draw<T, I>(grid: &mut Grid<T>, points: I, value: T) -> Option<(Point, Point)>
+where
+    T: Clone,
+    I: IntoIterator<Item = Point>,
+{
+    bounds(points.into_iter().inspect(|&point| {
+        assert!(point.0 >= 0 && point.1 >= 0);
+
+        grid.set((point.0 as usize, point.1 as usize), value.clone());
+    }))
+}
+
+/// Adds every point to `points` and returns their bounds.
+pub fn draw_sparse<I>(points: &mut HashSet<Point>, drawn: I) -> Option<(Point, Point)>
+where
+    I: IntoIterator<Item = Point>,
+{
+    bounds(drawn.into_iter().inspect(|&point| {
+        points.insert(point);
+    }))
+}
+
+pub struct LineIter {
+    current: Option<Point>,
+    to: Point,
+    delta: (isize, isize),
+    step: (isize, isize),
+    error: isize,
+}
+
+impl Iterator for LineIter {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let point = self.current?;
+
+        if point == self.to {
+            self.current = None;
+            return Some(point);
+        }
+
+        let mut next = point;
+        let doubled = 2 * self.error;
+
+        if doubled >= self.delta.1 {
+            self.error += self.delta.1;
+            next.0 += self.step.0;
+        }
+
+        if doubled <= self.delta.0 {
+            self.error += self.delta.0;
+            next.1 += self.step.1;
+        }
+
+        self.current = Some(next);
+        Some(point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_axis_aligned() {
+        assert_eq!(
+            line((498, 4), (498, 6)).collect::<Vec<Point>>(),
+            vec![(498, 4), (498, 5), (498, 6)]
+        );
+        assert_eq!(
+            line((2, 0), (-1, 0)).collect::<Vec<Point>>(),
+            vec![(2, 0), (1, 0), (0, 0), (-1, 0)]
+        );
+        assert_eq!(line((3, 3), (3, 3)).collect::<Vec<Point>>(), vec![(3, 3)]);
+    }
+
+    #[test]
+    fn test_diagonal() {
+        assert_eq!(
+            line((0, 3), (3, 0)).collect::<Vec<Point>>(),
+            vec![(0, 3), (1, 2), (2, 1), (3, 0)]
+        );
+    }
+
+    #[test]
+    fn test_bresenham() {
+        assert_eq!(
+            line((0, 0), (4, 2)).collect::<Vec<Point>>(),
+            vec![(0, 0), (1, 1), (2, 1), (3, 2), (4, 2)]
+        );
+        assert_eq!(
+            line((0, 0), (1, 5)).collect::<Vec<Point>>(),
+            vec![(0, 0), (0, 1), (0, 2), (1, 3), (1, 4), (1, 5)]
+        );
+    }
+
+    #[test]
+    fn test_polyline() {
+        assert_eq!(
+            polyline([(498, 4), (498, 6), (496, 6)]).collect::<Vec<Point>>(),
+            vec![(498, 4), (498, 5), (498, 6), (497, 6), (496, 6)]
+        );
+        assert_eq!(polyline([(1, 1)]).collect::<Vec<Point>>(), vec![(1, 1)]);
+        assert_eq!(polyline([]).count(), 0);
+    }
+
+    #[test]
+    fn test_bounds() {
+        assert_eq!(bounds([(3, -1), (0, 4), (2, 2)]), Some(((0, -1), (3, 4))));
+        assert_eq!(bounds([]), None);
+    }
+
+    #[test]
+    fn test_draw() {
+        let mut grid = Grid::new((4, 3), vec!['.'; 12]);
+        let drawn = draw(&mut grid, polyline([(0, 0), (2, 0), (2, 2)]), '#');
+
+        assert_eq!(drawn, Some(((0, 0), (2, 2))));
+        assert_eq!(grid.to_string(), "###.\n..#.\n..#.");
+    }
+
+    #[test]
+    fn test_draw_sparse() {
+        let mut points = HashSet::new();
+
+        assert_eq!(
+            draw_sparse(&mut points, line((-1, -1), (1, 1))),
+            Some(((-1, -1), (1, 1)))
+        );
+        assert_eq!(points, HashSet::from([(-1, -1), (0, 0), (1, 1)]));
+    }
+}
@@ -0,0 +1,400 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Rem, Sub, SubAssign};
+
+/// Primitive integer types.
+pub trait Integer:
+    Copy
+    + Ord
+    + Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, other)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+fn abs<T: Integer>(value: T) -> T {
+    if value < T::ZERO {
+        T::ZERO - value
+    } else {
+        value
+    }
+}
+
+/// Always non-negative, `gcd(0, 0)` is 0.
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    let (mut a, mut b) = (abs(a), abs(b));
+
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+
+    a
+}
+
+/// Always non-negative, 0 if either value is 0. Overflow panics in debug builds and wraps
+/// in release builds, see `checked_lcm`.
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    if a == T::ZERO || b == T::ZERO {
+        return T::ZERO;
+    }
+
+    abs(a / gcd(a, b) * b)
+}
+
+/// Same as `lcm`, `None` if the result does not fit in `T`.
+pub fn checked_lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+
+    let (a, b) = (abs(a), abs(b));
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+pub fn gcd_all<T, I>(values: I) -> T
+where
+    T: Integer,
+    I: IntoIterator<Item = T>,
+{
+    values.into_iter().fold(T::ZERO, gcd)
+}
+
+/// 1 for no values.
+pub fn lcm_all<T, I>(values: I) -> T
+where
+    T: Integer,
+    I: IntoIterator<Item = T>,
+{
+    values.into_iter().fold(T::ONE, lcm)
+}
+
+/// Returns `(g, x, y)` with `a * x + b * y = g = gcd(a, b)`.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// `x` in `0..modulus` with `a * x ≡ 1`, `None` unless `a` and `modulus` are coprime.
+pub fn mod_inv(a: i64, modulus: i64) -> Option<i64> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// `base.pow(exponent) % modulus` without overflowing.
+pub fn mod_pow(base: u64, mut exponent: u64, modulus: u64) -> u64 {
+    let modulus = modulus as u128;
+    let mut base = base as u128 % modulus;
+    let mut result = 1 % modulus;
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % modulus;
+        }
+
+        base = base * base % modulus;
+        exponent >>= 1;
+    }
+
+    result as u64
+}
+
+/// Solves `x ≡ residue (mod modulus)` for every `(residue, modulus)` pair. Returns the
+/// smallest non-negative `x` and the modulus of the solution, or `None` if the congruences
+/// contradict each other, a modulus is not positive or the combined modulus overflows an
+/// `i64`. Moduli need not be coprime.
+pub fn crt<I>(congruences: I) -> Option<(i64, i64)>
+where
+    I: IntoIterator<Item = (i64, i64)>,
+{
+    let mut solution: (i64, i64) = (0, 1);
+
+    for (residue, modulus) in congruences {
+        if modulus <= 0 {
+            return None;
+        }
+
+        let (g, p, _) = extended_gcd(solution.1, modulus);
+        let (value, known) = (solution.0 as i128, solution.1 as i128);
+        let (residue, modulus, g) = (residue as i128, modulus as i128, g as i128);
+
+        if (residue - value) % g != 0 {
+            return None;
+        }
+
+        let combined = known / g * modulus;
+        let step = (residue - value) / g % (modulus / g) * p as i128 % (modulus / g);
+        let value = (value + known * step).rem_euclid(combined);
+
+        solution = (i64::try_from(value).ok()?, i64::try_from(combined).ok()?);
+    }
+
+    Some(solution)
+}
+
+/// Integer modulo `M`, always kept in `0..M`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct ModInt<const M: u64>(u64);
+
+impl<const M: u64> ModInt<M> {
+    pub fn new(value: u64) -> Self {
+        ModInt(value % M)
+    }
+
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+
+    pub fn pow(&self, exponent: u64) -> Self {
+        ModInt(mod_pow(self.0, exponent, M))
+    }
+
+    /// `None` unless the value is coprime with `M`.
+    pub fn inv(&self) -> Option<Self> {
+        // Extended Euclid in i128, as `M` may not fit in an i64
+        let (mut old_r, mut r) = (self.0 as i128, M as i128);
+        let (mut old_x, mut x) = (1i128, 0i128);
+
+        while r != 0 {
+            let quotient = old_r / r;
+            (old_r, r) = (r, old_r - quotient * r);
+            (old_x, x) = (x, old_x - quotient * x);
+        }
+
+        (old_r == 1).then(|| ModInt(old_x.rem_euclid(M as i128) as u64))
+    }
+}
+
+impl<const M: u64> From<u64> for ModInt<M> {
+    fn from(value: u64) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<const M: u64> Debug for ModInt<M> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (mod {})", self.0, M)
+    }
+}
+
+impl<const M: u64> Display for ModInt<M> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<const M: u64> Add for ModInt<M> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        ModInt(((self.0 as u128 + other.0 as u128) % M as u128) as u64)
+    }
+}
+
+impl<const M: u64> Sub for ModInt<M> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl<const M: u64> Neg for ModInt<M> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        ModInt((M - self.0) % M)
+    }
+}
+
+impl<const M: u64> Mul for ModInt<M> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        ModInt((self.0 as u128 * other.0 as u128 % M as u128) as u64)
+    }
+}
+
+/// Panics if `other` has no inverse modulo `M`.
+impl<const M: u64> Div for ModInt<M> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self {
+        self * other.inv().expect("Divisor has no modular inverse")
+    }
+}
+
+impl<const M: u64> AddAssign for ModInt<M> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<const M: u64> SubAssign for ModInt<M> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<const M: u64> MulAssign for ModInt<M> {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(gcd(0u8, 0), 0);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(-4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+    }
+
+    #[test]
+    fn test_checked_lcm() {
+        assert_eq!(checked_lcm(4u8, 6), Some(12));
+        assert_eq!(checked_lcm(-4, 6), Some(12));
+        assert_eq!(checked_lcm(0, 6), Some(0));
+        assert_eq!(checked_lcm(16u8, 17), None);
+        assert_eq!(checked_lcm(u64::MAX, u64::MAX), Some(u64::MAX));
+    }
+
+    #[test]
+    fn test_gcd_lcm_all() {
+        assert_eq!(gcd_all([12usize, 18, 30]), 6);
+        assert_eq!(lcm_all([23usize, 19, 13, 17]), 96577);
+        assert_eq!(lcm_all([2usize, 4, 6]), 12);
+        assert_eq!(lcm_all(Vec::<usize>::new()), 1);
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        for (a, b) in [(240, 46), (-240, 46), (17, 5), (0, 9)] {
+            let (g, x, y) = extended_gcd(a, b);
+
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+
+    #[test]
+    fn test_mod_inv() {
+        assert_eq!(mod_inv(3, 11), Some(4));
+        assert_eq!(mod_inv(-3, 11), Some(7));
+        assert_eq!(mod_inv(6, 9), None);
+    }
+
+    #[test]
+    fn test_mod_pow() {
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(2, 0, 1), 0);
+        assert_eq!(mod_pow(u64::MAX, u64::MAX, 1_000_000_007), 254368884);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // Not coprime but consistent
+        assert_eq!(crt([(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([]), Some((0, 1)));
+    }
+
+    #[test]
+    fn test_crt_invalid() {
+        assert_eq!(crt([(1, 0)]), None);
+        assert_eq!(crt([(2, 3), (1, -5)]), None);
+
+        // Coprime with both 2 and 3, but only `big * 2` fits in an i64
+        let big = (1i64 << 62) - 57;
+        assert_eq!(
+            crt([(1, big), (0, 2)]).map(|(_, modulus)| modulus),
+            Some(big * 2)
+        );
+        assert_eq!(crt([(1, big), (2, 3)]), None);
+    }
+
+    #[test]
+    fn test_mod_int() {
+        type M7 = ModInt<7>;
+
+        let a = M7::new(5);
+        let b = M7::from(4);
+
+        assert_eq!((a + b).value(), 2);
+        assert_eq!((b - a).value(), 6);
+        assert_eq!((-M7::new(0)).value(), 0);
+        assert_eq!((a * b).value(), 6);
+        assert_eq!((a / b).value(), 3);
+        assert_eq!(a.pow(3).value(), 6);
+        assert_eq!(M7::new(0).inv(), None);
+
+        let mut c = a;
+        c += b;
+        c *= b;
+        c -= a;
+        assert_eq!(c.value(), 3);
+        assert_eq!(format!("{} {:?}", c, c), "3 3 (mod 7)");
+    }
+
+    #[test]
+    fn test_mod_int_large_modulus() {
+        // Largest prime below 2^64, beyond i64::MAX
+        type Big = ModInt<18_446_744_073_709_551_557>;
+
+        let a = Big::new(u64::MAX);
+        assert_eq!(a.value(), 58);
+        assert_eq!((a * a.inv().unwrap()).value(), 1);
+        assert_eq!(
+            Big::new(2).inv().unwrap().value(),
+            9_223_372_036_854_775_779
+        );
+        assert_eq!((Big::new(1) / Big::new(3) * Big::new(3)).value(), 1);
+        assert_eq!(Big::new(0).inv(), None);
+    }
+}
@@ -0,0 +1,182 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct MemoStats {
+    pub hits: usize,
+    pub misses: usize,
+    pub evictions: usize,
+}
+
+/// Cache for a function of hashable arguments, optionally bounded. Recursive functions
+/// take the memo as an argument and recurse through `get_or_compute`.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    values: HashMap<K, V>,
+    // Insertion order, only tracked when bounded
+    order: VecDeque<K>,
+    capacity: Option<usize>,
+    stats: MemoStats,
+}
+
+impl<K, V> Memo<K, V>
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+{
+    pub fn new() -> Self {
+        Memo {
+            values: HashMap::new(),
+            order: VecDeque::new(),
+            capacity: None,
+            stats: MemoStats::default(),
+        }
+    }
+
+    /// Keeps at most `capacity` values, evicting the oldest first.
+    pub fn bounded(capacity: usize) -> Self {
+        assert!(capacity > 0, "Capacity must be positive");
+
+        Memo {
+            capacity: Some(capacity),
+            ..Self::new()
+        }
+    }
+
+    /// Cached value for `key`, or the result of `compute` which may recurse through the
+    /// same memo.
+    pub fn get_or_compute<F>(&mut self, key: K, compute: F) -> V
+    where
+        F: FnOnce(&mut Self) -> V,
+    {
+        if let Some(value) = self.values.get(&key) {
+            self.stats.hits += 1;
+            return value.clone();
+        }
+
+        self.stats.misses += 1;
+        let value = compute(self);
+        self.insert(key, value.clone());
+
+        value
+    }
+
+    /// Cached value for `key`, without touching the statistics.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.values.get(key)
+    }
+
+    pub fn insert(&mut self, key: K, value: V) {
+        if self.values.insert(key.clone(), value).is_some() {
+            return;
+        }
+
+        if let Some(capacity) = self.capacity {
+            self.order.push_back(key);
+
+            while self.order.len() > capacity {
+                let oldest = self.order.pop_front().unwrap();
+                self.values.remove(&oldest);
+                self.stats.evictions += 1;
+            }
+        }
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        self.stats
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Drops every value, keeping the statistics.
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.order.clear();
+    }
+}
+
+impl<K, V> Default for Memo<K, V>
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Lattice paths from (0, 0) to (x, y)
+    fn paths(memo: &mut Memo<(u64, u64), u64>, x: u64, y: u64) -> u64 {
+        memo.get_or_compute((x, y), |memo| {
+            if x == 0 || y == 0 {
+                1
+            } else {
+                paths(memo, x - 1, y) + paths(memo, x, y - 1)
+            }
+        })
+    }
+
+    #[test]
+    fn test_recursion() {
+        let mut memo = Memo::new();
+
+        assert_eq!(paths(&mut memo, 16, 16), 601080390);
+        // Every cell of the 17x17 lattice except (0, 0) is computed exactly once
+        assert_eq!(memo.len(), 17 * 17 - 1);
+        assert_eq!(memo.stats().misses, memo.len());
+        assert_eq!(memo.stats().evictions, 0);
+        assert!(memo.stats().hits > 0);
+    }
+
+    #[test]
+    fn test_stats() {
+        let mut memo: Memo<&str, usize> = Memo::new();
+
+        assert_eq!(memo.get_or_compute("abc", |_| 3), 3);
+        assert_eq!(memo.get_or_compute("abc", |_| unreachable!()), 3);
+        assert_eq!(memo.get(&"abc"), Some(&3));
+        assert_eq!(
+            memo.stats(),
+            MemoStats {
+                hits: 1,
+                misses: 1,
+                evictions: 0
+            }
+        );
+
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!(memo.stats().hits, 1);
+    }
+
+    #[test]
+    fn test_bounded() {
+        let mut memo = Memo::bounded(2);
+
+        memo.insert(1, 'a');
+        memo.insert(2, 'b');
+        memo.insert(1, 'c');
+        memo.insert(3, 'd');
+
+        assert_eq!(memo.len(), 2);
+        assert_eq!(memo.get(&1), None);
+        assert_eq!(memo.get(&2), Some(&'b'));
+        assert_eq!(memo.get(&3), Some(&'d'));
+        assert_eq!(memo.stats().evictions, 1);
+
+        // Still correct when values get evicted mid-recursion
+        let mut memo = Memo::bounded(8);
+        assert_eq!(paths(&mut memo, 10, 10), 184756);
+        assert_eq!(memo.len(), 8);
+    }
+}
@@ -0,0 +1,296 @@
+//! Reads the block letters drawn by CRT and star message puzzles.
+
+use std::fmt::{Display, Formatter};
+
+use crate::Grid;
+
+const SMALL_FONT: [(char, &str); 18] = [
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+const LARGE_FONT: [(char, &str); 15] = [
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OcrError {
+    /// Lit rows, after trimming blank ones, match neither the 6 nor the 10 pixel font.
+    UnsupportedHeight(usize),
+    /// The glyph at `index`, drawn with `#` and `.`, is not in the font.
+    UnknownGlyph { index: usize, glyph: String },
+}
+
+impl Display for OcrError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OcrError::UnsupportedHeight(height) => {
+                write!(f, "No font is {} pixels high", height)
+            }
+            OcrError::UnknownGlyph { index, glyph } => {
+                write!(f, "Unknown glyph at position {}:\n{}", index, glyph)
+            }
+        }
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+/// Letters are told apart by the blank columns between them.
+pub fn recognize(image: &Grid<bool>) -> Result<String, OcrError> {
+    let (width, height) = image.size;
+    let lit_row = |y: usize| (0..width).any(|x| *image.get((x, y)));
+    let top = (0..height).find(|&y| lit_row(y)).unwrap_or(0);
+    let bottom = (0..height).rfind(|&y| lit_row(y)).map_or(0, |y| y + 1);
+    let lit_column = |x: usize| (top..bottom).any(|y| *image.get((x, y)));
+
+    let font: &[(char, &str)] = match bottom.saturating_sub(top) {
+        0 => return Ok(String::new()),
+        6 => &SMALL_FONT,
+        10 => &LARGE_FONT,
+        height => return Err(OcrError::UnsupportedHeight(height)),
+    };
+
+    let mut text = String::new();
+    let mut x = 0;
+
+    while x < width {
+        if !lit_column(x) {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        while x < width && lit_column(x) {
+            x += 1;
+        }
+
+        let glyph = (top..bottom)
+            .map(|y| {
+                (start..x)
+                    .map(|x| if *image.get((x, y)) { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        match font.iter().find(|(_, pattern)| *pattern == glyph) {
+            Some(&(letter, _)) => text.push(letter),
+            None => {
+                return Err(OcrError::UnknownGlyph {
+                    index: text.len(),
+                    glyph,
+                })
+            }
+        }
+    }
+
+    Ok(text)
+}
+
+/// Reads an image drawn with `#` for lit pixels, one line per row.
+pub fn recognize_str(image: &str) -> Result<String, OcrError> {
+    let lines: Vec<&str> = image
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .collect();
+    let width = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+
+    let cells = lines
+        .iter()
+        .flat_map(|line| {
+            let pixels = line.chars().map(|c| c == '#');
+            pixels.chain(std::iter::repeat(false)).take(width)
+        })
+        .collect();
+
+    recognize(&Grid::new((width, lines.len()), cells))
+}
+
+/// Reads lit points at any offset, e.g. once a star message has converged.
+pub fn recognize_points<I>(points: I) -> Result<String, OcrError>
+where
+    I: IntoIterator<Item = (isize, isize)>,
+{
+    let points: Vec<(isize, isize)> = points.into_iter().collect();
+
+    let Some(min_x) = points.iter().map(|point| point.0).min() else {
+        return Ok(String::new());
+    };
+    let max_x = points.iter().map(|point| point.0).max().unwrap();
+    let min_y = points.iter().map(|point| point.1).min().unwrap();
+    let max_y = points.iter().map(|point| point.1).max().unwrap();
+
+    let size = ((max_x - min_x + 1) as usize, (max_y - min_y + 1) as usize);
+    let mut image = Grid::new(size, vec![false; size.0 * size.1]);
+
+    for (x, y) in points {
+        image.set(((x - min_x) as usize, (y - min_y) as usize), true);
+    }
+
+    recognize(&image)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HELLO: &str = "
+#..#.####.#....#.....##.
+#..#.#....#....#....#..#
+####.###..#....#....#..#
+#..#.#....#....#....#..#
+#..#.#....#....#....#..#
+#..#.####.####.####..##.
+";
+
+    #[test]
+    fn test_recognize_str() {
+        assert_eq!(recognize_str(HELLO), Ok("HELLO".to_string()));
+        assert_eq!(recognize_str(""), Ok(String::new()));
+    }
+
+    #[test]
+    fn test_recognize_narrow_letters() {
+        let image = "
+.###..#...#
+..#...#...#
+..#....#.#.
+..#.....#..
+..#.....#..
+.###....#..
+";
+
+        assert_eq!(recognize_str(image), Ok("IY".to_string()));
+    }
+
+    #[test]
+    fn test_recognize_large_font() {
+        let (_, x) = LARGE_FONT[13];
+        let (_, n) = LARGE_FONT[10];
+        let image: String = x
+            .lines()
+            .zip(n.lines())
+            .map(|(x, n)| format!("{}..{}\n", x, n))
+            .collect();
+
+        assert_eq!(recognize_str(&image), Ok("XN".to_string()));
+    }
+
+    #[test]
+    fn test_recognize_points() {
+        let points = HELLO.lines().skip(1).enumerate().flat_map(|(y, line)| {
+            line.chars()
+                .enumerate()
+                .filter(|&(_, c)| c == '#')
+                .map(move |(x, _)| (x as isize - 10, y as isize + 3))
+        });
+
+        assert_eq!(recognize_points(points), Ok("HELLO".to_string()));
+        assert_eq!(recognize_points([]), Ok(String::new()));
+    }
+
+    #[test]
+    fn test_unknown_glyph() {
+        let image = "
+#..#.####
+#..#.#..#
+####.#..#
+#..#.#..#
+#..#.#..#
+#..#.####
+";
+
+        assert_eq!(
+            recognize_str(image),
+            Err(OcrError::UnknownGlyph {
+                index: 1,
+                glyph: "####\n#..#\n#..#\n#..#\n#..#\n####".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_unsupported_height() {
+        assert_eq!(
+            recognize_str("#\n#\n#"),
+            Err(OcrError::UnsupportedHeight(3))
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use rayon::prelude::*;
+
+use crate::{Coord, Grid};
+
+/// Parallel counterparts of the sequential iterators, enabled by the `parallel` feature.
+///
+/// All of them are indexed: `collect`, `find_first` and friends see cells in row-major
+/// order regardless of scheduling. `reduce` and `sum` only give reproducible results when
+/// the operation is associative, which rules out e.g. floating point sums.
+impl<T: Sync> Grid<T> {
+    pub fn par_iter(&self) -> impl IndexedParallelIterator<Item = (Coord, &T)> + '_ {
+        let width = self.size.0;
+
+        self.cells()
+            .par_iter()
+            .enumerate()
+            .map(move |(index, cell)| ((index % width, index / width), cell))
+    }
+
+    pub fn par_rows(&self) -> impl IndexedParallelIterator<Item = &[T]> + '_ {
+        self.cells().par_chunks(self.size.0.max(1))
+    }
+
+    /// Builds a grid of the same size and topology from each cell and its coordinate.
+    pub fn par_map<U, F>(&self, f: F) -> Grid<U>
+    where
+        U: Send,
+        F: Fn(Coord, &T) -> U + Sync + Send,
+    {
+        let cells = self
+            .par_iter()
+            .map(|(coord, cell)| f(coord, cell))
+            .collect();
+
+        Grid::new(self.size, cells).with_topology(self.topology())
+    }
+}
+
+impl<T: Send> Grid<T> {
+    pub fn par_iter_mut(&mut self) -> impl IndexedParallelIterator<Item = (Coord, &mut T)> + '_ {
+        let width = self.size.0;
+
+        self.cells_mut()
+            .par_iter_mut()
+            .enumerate()
+            .map(move |(index, cell)| ((index % width, index / width), cell))
+    }
+
+    pub fn par_rows_mut(&mut self) -> impl IndexedParallelIterator<Item = &mut [T]> + '_ {
+        let width = self.size.0.max(1);
+
+        self.cells_mut().par_chunks_mut(width)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Topology;
+
+    use super::*;
+
+    fn create_grid() -> Grid<usize> {
+        Grid::new((3, 2), (0..6).collect())
+    }
+
+    #[test]
+    fn test_par_iter() {
+        let grid = create_grid();
+
+        assert_eq!(
+            grid.par_iter().collect::<Vec<(Coord, &usize)>>(),
+            grid.iter().collect::<Vec<(Coord, &usize)>>()
+        );
+        assert_eq!(grid.par_iter().map(|(_, &cell)| cell).sum::<usize>(), 15);
+    }
+
+    #[test]
+    fn test_par_iter_mut() {
+        let mut grid = create_grid();
+        grid.par_iter_mut()
+            .for_each(|((x, y), cell)| *cell += x * y);
+
+        assert_eq!(grid, Grid::new((3, 2), vec![0, 1, 2, 3, 5, 7]));
+    }
+
+    #[test]
+    fn test_par_rows() {
+        let mut grid = create_grid();
+
+        assert_eq!(
+            grid.par_rows()
+                .map(|row| row.iter().sum())
+                .collect::<Vec<usize>>(),
+            vec![3, 12]
+        );
+
+        grid.par_rows_mut().for_each(|row| row.reverse());
+        assert_eq!(grid, Grid::new((3, 2), vec![2, 1, 0, 5, 4, 3]));
+    }
+
+    #[test]
+    fn test_par_map() {
+        let grid = create_grid().with_topology(Topology::Torus);
+        let mapped = grid.par_map(|(x, _), &cell| (cell * 10 + x).to_string());
+
+        assert_eq!(mapped.get((2, 1)), "52");
+        assert_eq!(mapped.topology(), Topology::Torus);
+        assert_eq!(mapped.size, grid.size);
+    }
+}
@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{BufWriter, Result, Write};
+use std::path::Path;
+
+use crate::{Coord, Grid};
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (index, cell) in self.iter().map(|(_, cell)| cell).enumerate() {
+            if index > 0 && 0 == index % self.size.0 {
+                writeln!(f)?;
+            }
+
+            write!(f, "{}", cell)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> Grid<T> {
+    /// Renders each cell as the char returned by `cell`.
+    pub fn render<F>(&self, cell: F) -> Render<'_, T, F>
+    where
+        F: Fn(&T) -> char,
+    {
+        Render {
+            grid: self,
+            cell,
+            axes: false,
+            highlights: HashMap::new(),
+        }
+    }
+
+    /// Writes a binary PGM image, `shade` maps each cell to a grey level.
+    pub fn write_pgm<W, F>(&self, mut writer: W, shade: F) -> Result<()>
+    where
+        W: Write,
+        F: Fn(&T) -> u8,
+    {
+        write!(writer, "P5\n{} {}\n255\n", self.size.0, self.size.1)?;
+
+        let pixels: Vec<u8> = self.iter().map(|(_, cell)| shade(cell)).collect();
+        writer.write_all(&pixels)
+    }
+
+    /// Writes a binary PPM image, `color` maps each cell to an RGB triplet.
+    pub fn write_ppm<W, F>(&self, mut writer: W, color: F) -> Result<()>
+    where
+        W: Write,
+        F: Fn(&T) -> (u8, u8, u8),
+    {
+        write!(writer, "P6\n{} {}\n255\n", self.size.0, self.size.1)?;
+
+        let pixels: Vec<u8> = self
+            .iter()
+            .flat_map(|(_, cell)| {
+                let (r, g, b) = color(cell);
+                [r, g, b]
+            })
+            .collect();
+        writer.write_all(&pixels)
+    }
+
+    pub fn save_pgm<P, F>(&self, path: P, shade: F) -> Result<()>
+    where
+        P: AsRef<Path>,
+        F: Fn(&T) -> u8,
+    {
+        self.write_pgm(BufWriter::new(File::create(path)?), shade)
+    }
+
+    pub fn save_ppm<P, F>(&self, path: P, color: F) -> Result<()>
+    where
+        P: AsRef<Path>,
+        F: Fn(&T) -> (u8, u8, u8),
+    {
+        self.write_ppm(BufWriter::new(File::create(path)?), color)
+    }
+}
+
+pub struct Render<'a, T, F> {
+    grid: &'a Grid<T>,
+    cell: F,
+    axes: bool,
+    highlights: HashMap<Coord, char>,
+}
+
+impl<'a, T, F> Render<'a, T, F>
+where
+    F: Fn(&T) -> char,
+{
+    /// Prints x indexes as vertical digits above the grid and y indexes on its left.
+    pub fn with_axes(mut self) -> Self {
+        self.axes = true;
+        self
+    }
+
+    /// Draws `mark` instead of the cell at each of `coords`, e.g. to show a path.
+    pub fn highlight<I>(mut self, coords: I, mark: char) -> Self
+    where
+        I: IntoIterator<Item = Coord>,
+    {
+        self.highlights
+            .extend(coords.into_iter().map(|coord| (coord, mark)));
+        self
+    }
+}
+
+impl<'a, T, F> Display for Render<'a, T, F>
+where
+    F: Fn(&T) -> char,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (width, height) = self.grid.size;
+        let label_width = (height.max(1) - 1).to_string().len();
+
+        if self.axes {
+            let digits = (width.max(1) - 1).to_string().len();
+
+            for digit in 0..digits {
+                write!(f, "{:label_width$} ", "")?;
+
+                for x in 0..width {
+                    let x = format!("{:>digits$}", x);
+                    write!(f, "{}", &x[digit..=digit])?;
+                }
+
+                writeln!(f)?;
+            }
+        }
+
+        for y in 0..height {
+            if y > 0 {
+                writeln!(f)?;
+            }
+
+            if self.axes {
+                write!(f, "{:>label_width$} ", y)?;
+            }
+
+            for x in 0..width {
+                let mark = match self.highlights.get(&(x, y)) {
+                    Some(&mark) => mark,
+                    None => (self.cell)(self.grid.get((x, y))),
+                };
+
+                write!(f, "{}", mark)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input_grid;
+
+    use super::*;
+
+    fn create_map() -> Grid<char> {
+        input_grid(
+            "
+#..#
+.##.
+#..#
+            ",
+        )
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(create_map().to_string(), "#..#\n.##.\n#..#");
+        assert_eq!(
+            Grid::new((3, 2), vec![1, 2, 3, 4, 5, 6]).to_string(),
+            "123\n456"
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let grid = Grid::new((3, 2), vec![true, false, true, false, true, false]);
+
+        assert_eq!(
+            grid.render(|&lit| if lit { '█' } else { ' ' }).to_string(),
+            "█ █\n █ "
+        );
+    }
+
+    #[test]
+    fn test_render_highlight() {
+        let map = create_map();
+
+        assert_eq!(
+            map.render(|&c| c)
+                .highlight([(1, 0), (2, 0)], 'o')
+                .highlight([(3, 1)], 'x')
+                .to_string(),
+            "#oo#\n.##x\n#..#"
+        );
+    }
+
+    #[test]
+    fn test_render_with_axes() {
+        let grid = Grid::new((12, 2), vec!['.'; 24]);
+
+        assert_eq!(
+            grid.render(|&c| c).with_axes().to_string(),
+            "            11
+  012345678901
+0 ............
+1 ............"
+        );
+    }
+
+    #[test]
+    fn test_write_pgm() {
+        let mut output = Vec::new();
+
+        create_map()
+            .write_pgm(&mut output, |&c| if c == '#' { 255 } else { 0 })
+            .unwrap();
+
+        let mut expected = b"P5\n4 3\n255\n".to_vec();
+        expected.extend([255, 0, 0, 255, 0, 255, 255, 0, 255, 0, 0, 255]);
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_write_ppm() {
+        let mut output = Vec::new();
+
+        Grid::new((2, 1), vec![1, 2])
+            .write_ppm(&mut output, |&v| (v, v * 10, v * 100))
+            .unwrap();
+
+        let mut expected = b"P6\n2 1\n255\n".to_vec();
+        expected.extend([1, 10, 100, 2, 20, 200]);
+        assert_eq!(output, expected);
+    }
+}
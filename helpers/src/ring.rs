@@ -0,0 +1,411 @@
+use std::ops::{Index, IndexMut};
+
+/// Stable reference to an element of a `Ring`, never reused once removed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Handle(usize);
+
+#[derive(Debug, Clone)]
+struct Node<T> {
+    value: Option<T>,
+    prev: usize,
+    next: usize,
+}
+
+/// Circular doubly-linked list stored in an arena, elements are reached through handles.
+#[derive(Debug, Clone)]
+pub struct Ring<T> {
+    nodes: Vec<Node<T>>,
+    head: Option<usize>,
+    len: usize,
+}
+
+impl<T> Ring<T> {
+    pub fn new() -> Self {
+        Ring {
+            nodes: Vec::new(),
+            head: None,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// First element pushed and still in the ring. Moving it keeps it the head.
+    pub fn head(&self) -> Option<Handle> {
+        self.head.map(Handle)
+    }
+
+    /// Appends `value` before the head, i.e. at the end of the ring.
+    pub fn push(&mut self, value: T) -> Handle {
+        match self.head {
+            Some(head) => self.insert_after(Handle(self.nodes[head].prev), value),
+            None => {
+                let index = self.allocate(value);
+                self.head = Some(index);
+                self.len = 1;
+
+                Handle(index)
+            }
+        }
+    }
+
+    pub fn insert_after(&mut self, after: Handle, value: T) -> Handle {
+        self.check(after);
+
+        let index = self.allocate(value);
+        self.link_after(index, after.0);
+
+        Handle(index)
+    }
+
+    /// Panics if `handle` was already removed.
+    pub fn remove(&mut self, handle: Handle) -> T {
+        self.check(handle);
+        if self.head == Some(handle.0) {
+            self.head = (self.len > 1).then_some(self.nodes[handle.0].next);
+        }
+        self.unlink(handle.0);
+
+        self.nodes[handle.0].value.take().unwrap()
+    }
+
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        self.nodes.get(handle.0)?.value.as_ref()
+    }
+
+    pub fn get_mut(&mut self, handle: Handle) -> Option<&mut T> {
+        self.nodes.get_mut(handle.0)?.value.as_mut()
+    }
+
+    pub fn next(&self, handle: Handle) -> Handle {
+        self.check(handle);
+        Handle(self.nodes[handle.0].next)
+    }
+
+    pub fn prev(&self, handle: Handle) -> Handle {
+        self.check(handle);
+        Handle(self.nodes[handle.0].prev)
+    }
+
+    /// Handle `offset` positions away, backwards for negative offsets.
+    pub fn advance(&self, handle: Handle, offset: isize) -> Handle {
+        self.check(handle);
+
+        let steps = offset.rem_euclid(self.len as isize) as usize;
+        self.walk(handle.0, steps, self.len)
+    }
+
+    /// Moves the element after `after`.
+    pub fn move_after(&mut self, handle: Handle, after: Handle) {
+        self.check(handle);
+        self.check(after);
+
+        if handle != after {
+            self.unlink(handle.0);
+            self.link_after(handle.0, after.0);
+        }
+    }
+
+    /// Moves the element forward past `offset` other elements, backwards for negative
+    /// offsets. Offsets wrap around the `len - 1` other elements, not the whole ring.
+    pub fn move_past(&mut self, handle: Handle, offset: isize) {
+        self.check(handle);
+
+        let others = self.len - 1;
+        if others == 0 {
+            return;
+        }
+
+        let steps = offset.rem_euclid(others as isize) as usize;
+        if steps == 0 {
+            return;
+        }
+
+        let prev = self.nodes[handle.0].prev;
+        self.unlink(handle.0);
+        let after = self.walk(prev, steps, others);
+        self.link_after(handle.0, after.0);
+    }
+
+    /// Handles of the elements still in the ring, in insertion order.
+    pub fn handles(&self) -> Vec<Handle> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.value.is_some())
+            .map(|(index, _)| Handle(index))
+            .collect()
+    }
+
+    /// Values once around the ring from the head.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            ring: self,
+            current: self.head,
+            remaining: self.len,
+        }
+    }
+
+    /// Values once around the ring starting at `handle`.
+    pub fn iter_from(&self, handle: Handle) -> Iter<'_, T> {
+        self.check(handle);
+
+        Iter {
+            ring: self,
+            current: Some(handle.0),
+            remaining: self.len,
+        }
+    }
+
+    fn check(&self, handle: Handle) {
+        assert!(
+            self.get(handle).is_some(),
+            "Handle {:?} is not in the ring",
+            handle
+        );
+    }
+
+    fn allocate(&mut self, value: T) -> usize {
+        let index = self.nodes.len();
+        self.nodes.push(Node {
+            value: Some(value),
+            prev: index,
+            next: index,
+        });
+
+        index
+    }
+
+    fn link_after(&mut self, index: usize, after: usize) {
+        let next = self.nodes[after].next;
+
+        self.nodes[index].prev = after;
+        self.nodes[index].next = next;
+        self.nodes[after].next = index;
+        self.nodes[next].prev = index;
+        self.len += 1;
+    }
+
+    /// Takes the node out of the links, leaving `head` to the caller.
+    fn unlink(&mut self, index: usize) {
+        let Node { prev, next, .. } = self.nodes[index];
+
+        self.nodes[prev].next = next;
+        self.nodes[next].prev = prev;
+        self.len -= 1;
+    }
+
+    /// Node `steps` forward from `index` in a ring of `len`, walking the shorter way.
+    fn walk(&self, mut index: usize, steps: usize, len: usize) -> Handle {
+        if steps <= len / 2 {
+            for _ in 0..steps {
+                index = self.nodes[index].next;
+            }
+        } else {
+            for _ in steps..len {
+                index = self.nodes[index].prev;
+            }
+        }
+
+        Handle(index)
+    }
+}
+
+impl<T> Default for Ring<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `handles` returns the elements' handles in the order of the values.
+impl<T> FromIterator<T> for Ring<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut ring = Ring::new();
+        for value in iter {
+            ring.push(value);
+        }
+
+        ring
+    }
+}
+
+/// Panics if `handle` was removed.
+impl<T> Index<Handle> for Ring<T> {
+    type Output = T;
+
+    fn index(&self, handle: Handle) -> &T {
+        self.check(handle);
+        self.get(handle).unwrap()
+    }
+}
+
+impl<T> IndexMut<Handle> for Ring<T> {
+    fn index_mut(&mut self, handle: Handle) -> &mut T {
+        self.check(handle);
+        self.get_mut(handle).unwrap()
+    }
+}
+
+pub struct Iter<'a, T> {
+    ring: &'a Ring<T>,
+    current: Option<usize>,
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let node = &self.ring.nodes[self.current?];
+        self.current = Some(node.next);
+        self.remaining -= 1;
+
+        node.value.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(ring: &Ring<isize>) -> Vec<isize> {
+        ring.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut ring = Ring::new();
+        assert!(ring.is_empty());
+
+        let a = ring.push(1);
+        let c = ring.push(3);
+        let b = ring.insert_after(a, 2);
+        assert_eq!(values(&ring), vec![1, 2, 3]);
+        assert_eq!(ring.next(c), a);
+        assert_eq!(ring.prev(a), c);
+
+        assert_eq!(ring.remove(a), 1);
+        assert_eq!(ring.head(), Some(b));
+        assert_eq!(ring.get(a), None);
+        assert_eq!(values(&ring), vec![2, 3]);
+
+        ring.remove(b);
+        ring.remove(c);
+        assert!(ring.is_empty());
+        assert_eq!(ring.head(), None);
+        assert!(values(&ring).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "is not in the ring")]
+    fn test_removed_handle() {
+        let mut ring: Ring<isize> = [1, 2].into_iter().collect();
+        let head = ring.head().unwrap();
+
+        ring.remove(head);
+        ring.next(head);
+    }
+
+    #[test]
+    fn test_handles() {
+        let mut ring: Ring<char> = "abcd".chars().collect();
+        let handles = ring.handles();
+
+        assert_eq!(
+            handles
+                .iter()
+                .map(|&handle| ring[handle])
+                .collect::<String>(),
+            "abcd"
+        );
+
+        ring.move_after(handles[0], handles[3]);
+        ring.remove(handles[2]);
+        let e = ring.insert_after(handles[1], 'e');
+
+        assert_eq!(ring.handles(), vec![handles[0], handles[1], handles[3], e]);
+    }
+
+    #[test]
+    fn test_iter_from() {
+        let ring: Ring<isize> = (0..5).collect();
+        let start = ring.advance(ring.head().unwrap(), -2);
+
+        assert_eq!(ring[start], 3);
+        assert_eq!(
+            ring.iter_from(start).copied().collect::<Vec<isize>>(),
+            vec![3, 4, 0, 1, 2]
+        );
+    }
+
+    #[test]
+    fn test_move() {
+        let mut ring: Ring<isize> = (0..5).collect();
+        let handles = ring.handles();
+
+        ring.move_after(handles[0], handles[2]);
+        assert_eq!(values(&ring), vec![0, 3, 4, 1, 2]);
+
+        ring.move_past(handles[3], 1);
+        assert_eq!(values(&ring), vec![0, 4, 3, 1, 2]);
+
+        // One full lap over the 4 other elements changes nothing
+        ring.move_past(handles[3], -4);
+        assert_eq!(values(&ring), vec![0, 4, 3, 1, 2]);
+
+        ring.move_past(handles[2], -3);
+        ring[handles[2]] = 20;
+        assert_eq!(
+            ring.iter_from(handles[0]).copied().collect::<Vec<isize>>(),
+            vec![0, 20, 4, 3, 1]
+        );
+    }
+
+    #[test]
+    fn test_move_head() {
+        let mut ring: Ring<char> = "abcd".chars().collect();
+        let head = ring.head().unwrap();
+
+        ring.move_past(head, 1);
+        assert_eq!(ring.head(), Some(head));
+        assert_eq!(ring.iter().collect::<String>(), "acdb");
+        assert_eq!(ring.prev(head), ring.handles()[1]);
+
+        ring.move_after(head, ring.handles()[3]);
+        assert_eq!(ring.head(), Some(head));
+        assert_eq!(ring.iter().collect::<String>(), "abcd");
+
+        // Pushing still appends before the head
+        ring.push('e');
+        assert_eq!(ring.iter().collect::<String>(), "abcde");
+    }
+
+    #[test]
+    fn test_mixing() {
+        let numbers = [1, 2, -3, 3, -2, 0, 4];
+        let mut ring: Ring<isize> = numbers.into_iter().collect();
+        let handles = ring.handles();
+
+        for &handle in &handles {
+            ring.move_past(handle, ring[handle]);
+        }
+
+        let zero = handles[5];
+        let coordinates: Vec<isize> = [1000, 2000, 3000]
+            .iter()
+            .map(|&offset| ring[ring.advance(zero, offset)])
+            .collect();
+
+        assert_eq!(coordinates, vec![4, -3, 2]);
+    }
+}
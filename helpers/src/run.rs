@@ -1,18 +1,28 @@
 use std::fmt::Display;
 use std::io::{stdout, Write};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use crate::instant::BasicInstant;
 
-pub fn run<S, R>(name: &str, solver: S)
+/// Outcome of one `run`, with the solution as printed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Report {
+    pub name: String,
+    pub solution: String,
+    pub elapsed: Duration,
+}
+
+/// Prints the solution and how long `solver` took, and returns them as a report.
+pub fn run<S, R>(name: &str, solver: S) -> Report
 where
     R: Display,
     S: Fn() -> R,
 {
-    raw_run(stdout(), Instant::now(), name, solver);
+    raw_run(stdout(), Instant::now(), name, solver)
 }
 
-fn raw_run<W, N, S, R>(mut writer: W, now: N, name: &str, solver: S)
+fn raw_run<W, N, S, R>(mut writer: W, now: N, name: &str, solver: S) -> Report
 where
     W: Write,
     N: BasicInstant,
@@ -20,15 +30,20 @@ where
     R: Display,
 {
     let solution = solver();
+    let report = Report {
+        name: name.to_string(),
+        solution: solution.to_string(),
+        elapsed: now.elapsed(),
+    };
 
     write!(
         &mut writer,
         "{}: {} ({:.2?})\n\n",
-        name,
-        solution,
-        now.elapsed()
+        report.name, report.solution, report.elapsed
     )
-    .expect("Something went wrong writing the solution!")
+    .expect("Something went wrong writing the solution!");
+
+    report
 }
 
 #[cfg(test)]
@@ -44,16 +59,39 @@ mod tests {
         let mut output = Vec::new();
         let input = [1721, 979, 366];
         fn solver(input: &[i32; 3]) -> i32 {
-            input
-                .iter()
-                .fold(0, |accumulator, entry| accumulator + entry)
+            input.iter().sum()
         }
 
-        raw_run(&mut output, instant::fake::Instant::now(), "test", || {
+        let report = raw_run(&mut output, instant::fake::Instant::now(), "test", || {
             solver(&input)
         });
         let output = String::from_utf8(output).expect("Not UTF-8");
 
         assert_eq!(output, "test: 3066 (10.00ms)\n\n");
+        assert_eq!(
+            report,
+            Report {
+                name: "test".to_string(),
+                solution: "3066".to_string(),
+                elapsed: Duration::from_millis(10),
+            }
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_report_serde() {
+        let report = Report {
+            name: "part1".to_string(),
+            solution: "3066".to_string(),
+            elapsed: Duration::from_micros(1500),
+        };
+        let json = serde_json::to_string(&report).unwrap();
+
+        assert_eq!(
+            json,
+            r#"{"name":"part1","solution":"3066","elapsed":{"secs":0,"nanos":1500000}}"#
+        );
+        assert_eq!(serde_json::from_str::<Report>(&json).unwrap(), report);
     }
 }
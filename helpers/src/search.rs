@@ -0,0 +1,494 @@
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::iter::once;
+use std::ops::Add;
+
+/// Cost of a path, `Default` must be the zero value.
+pub trait Cost: Copy + Ord + Default + Add<Output = Self> {}
+
+impl<C: Copy + Ord + Default + Add<Output = C>> Cost for C {}
+
+pub fn bfs<S, F, I, G>(start: S, successors: F, is_goal: G) -> Option<(usize, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+    G: FnMut(&S) -> bool,
+{
+    bfs_multi(once(start), successors, is_goal)
+}
+
+pub fn bfs_multi<S, T, F, I, G>(
+    starts: T,
+    mut successors: F,
+    mut is_goal: G,
+) -> Option<(usize, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    T: IntoIterator<Item = S>,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+    G: FnMut(&S) -> bool,
+{
+    let mut nodes = Nodes::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if let Some(index) = nodes.insert(start, None, 0) {
+            queue.push_back(index);
+        }
+    }
+
+    while let Some(index) = queue.pop_front() {
+        let (state, _, steps) = &nodes.list[index];
+
+        if is_goal(state) {
+            return Some((*steps, nodes.path(index)));
+        }
+
+        let steps = steps + 1;
+
+        for next in successors(state) {
+            if let Some(next_index) = nodes.insert(next, Some(index), steps) {
+                queue.push_back(next_index);
+            }
+        }
+    }
+
+    None
+}
+
+/// Steps to every state reachable from `start`.
+pub fn bfs_all<S, F, I>(start: S, successors: F) -> HashMap<S, usize>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+{
+    bfs_all_multi(once(start), successors)
+}
+
+pub fn bfs_all_multi<S, T, F, I>(starts: T, mut successors: F) -> HashMap<S, usize>
+where
+    S: Clone + Eq + Hash,
+    T: IntoIterator<Item = S>,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+{
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if let Entry::Vacant(entry) = distances.entry(start.clone()) {
+            entry.insert(0);
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((state, steps)) = queue.pop_front() {
+        for next in successors(&state) {
+            if let Entry::Vacant(entry) = distances.entry(next.clone()) {
+                entry.insert(steps + 1);
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+
+    distances
+}
+
+pub fn dijkstra<S, C, F, I, G>(start: S, successors: F, is_goal: G) -> Option<(C, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    G: FnMut(&S) -> bool,
+{
+    astar_multi(once(start), successors, |_| C::default(), is_goal)
+}
+
+pub fn dijkstra_multi<S, C, T, F, I, G>(starts: T, successors: F, is_goal: G) -> Option<(C, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    T: IntoIterator<Item = S>,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    G: FnMut(&S) -> bool,
+{
+    astar_multi(starts, successors, |_| C::default(), is_goal)
+}
+
+/// Cost to every state reachable from `start`.
+pub fn dijkstra_all<S, C, F, I>(start: S, successors: F) -> HashMap<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+{
+    dijkstra_all_multi(once(start), successors)
+}
+
+pub fn dijkstra_all_multi<S, C, T, F, I>(starts: T, mut successors: F) -> HashMap<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    T: IntoIterator<Item = S>,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut nodes = Nodes::new();
+    let mut heap = BinaryHeap::new();
+
+    for start in starts {
+        if let Some(index) = nodes.insert(start, None, C::default()) {
+            heap.push(Visit::new(index, C::default(), C::default()));
+        }
+    }
+
+    while let Some(visit) = heap.pop() {
+        let (state, _, cost) = &nodes.list[visit.index];
+
+        if visit.cost > *cost {
+            continue;
+        }
+
+        let cost = *cost;
+
+        for (next, step_cost) in successors(state) {
+            let next_cost = cost + step_cost;
+
+            if let Some(next_index) = nodes.relax(next, visit.index, next_cost) {
+                heap.push(Visit::new(next_index, next_cost, next_cost));
+            }
+        }
+    }
+
+    nodes
+        .list
+        .into_iter()
+        .map(|(state, _, cost)| (state, cost))
+        .collect()
+}
+
+/// `heuristic` must never overestimate the remaining cost to a goal.
+pub fn astar<S, C, F, I, H, G>(
+    start: S,
+    successors: F,
+    heuristic: H,
+    is_goal: G,
+) -> Option<(C, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    H: FnMut(&S) -> C,
+    G: FnMut(&S) -> bool,
+{
+    astar_multi(once(start), successors, heuristic, is_goal)
+}
+
+pub fn astar_multi<S, C, T, F, I, H, G>(
+    starts: T,
+    mut successors: F,
+    mut heuristic: H,
+    mut is_goal: G,
+) -> Option<(C, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    C: Cost,
+    T: IntoIterator<Item = S>,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    H: FnMut(&S) -> C,
+    G: FnMut(&S) -> bool,
+{
+    let mut nodes = Nodes::new();
+    let mut heap = BinaryHeap::new();
+
+    for start in starts {
+        let estimate = heuristic(&start);
+
+        if let Some(index) = nodes.insert(start, None, C::default()) {
+            heap.push(Visit::new(index, C::default(), estimate));
+        }
+    }
+
+    while let Some(visit) = heap.pop() {
+        let (state, _, cost) = &nodes.list[visit.index];
+
+        if visit.cost > *cost {
+            continue;
+        }
+
+        if is_goal(state) {
+            return Some((*cost, nodes.path(visit.index)));
+        }
+
+        let cost = *cost;
+
+        for (next, step_cost) in successors(state) {
+            let next_cost = cost + step_cost;
+            let estimate = next_cost + heuristic(&next);
+
+            if let Some(next_index) = nodes.relax(next, visit.index, next_cost) {
+                heap.push(Visit::new(next_index, next_cost, estimate));
+            }
+        }
+    }
+
+    None
+}
+
+/// Visited states with their parent and cost, indexed to avoid cloning states into the queues.
+struct Nodes<S, C> {
+    list: Vec<(S, Option<usize>, C)>,
+    indexes: HashMap<S, usize>,
+}
+
+impl<S: Clone + Eq + Hash, C: Copy + Ord> Nodes<S, C> {
+    fn new() -> Self {
+        Nodes {
+            list: Vec::new(),
+            indexes: HashMap::new(),
+        }
+    }
+
+    /// Returns the new index, or `None` if the state was already known.
+    fn insert(&mut self, state: S, parent: Option<usize>, cost: C) -> Option<usize> {
+        match self.indexes.entry(state.clone()) {
+            Entry::Occupied(_) => None,
+            Entry::Vacant(entry) => {
+                entry.insert(self.list.len());
+                self.list.push((state, parent, cost));
+
+                Some(self.list.len() - 1)
+            }
+        }
+    }
+
+    /// Returns the index of the state if `cost` improves the known one.
+    fn relax(&mut self, state: S, parent: usize, cost: C) -> Option<usize> {
+        match self.indexes.get(&state) {
+            Some(&index) => {
+                let node = &mut self.list[index];
+
+                if cost < node.2 {
+                    node.1 = Some(parent);
+                    node.2 = cost;
+
+                    Some(index)
+                } else {
+                    None
+                }
+            }
+            None => self.insert(state, Some(parent), cost),
+        }
+    }
+
+    fn path(&self, mut index: usize) -> Vec<S> {
+        let mut path = vec![self.list[index].0.clone()];
+
+        while let Some(parent) = self.list[index].1 {
+            path.push(self.list[parent].0.clone());
+            index = parent;
+        }
+
+        path.reverse();
+        path
+    }
+}
+
+struct Visit<C> {
+    index: usize,
+    cost: C,
+    estimate: C,
+}
+
+impl<C> Visit<C> {
+    fn new(index: usize, cost: C, estimate: C) -> Self {
+        Visit {
+            index,
+            cost,
+            estimate,
+        }
+    }
+}
+
+impl<C: Ord> PartialEq for Visit<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<C: Ord> Eq for Visit<C> {}
+
+impl<C: Ord> PartialOrd for Visit<C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<C: Ord> Ord for Visit<C> {
+    // Reversed so the `BinaryHeap` pops the lowest estimate first, then the highest cost
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate
+            .cmp(&self.estimate)
+            .then_with(|| self.cost.cmp(&other.cost))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{input_grid, Coord, Grid};
+
+    use super::*;
+
+    fn create_maze() -> Grid<char> {
+        input_grid(
+            "
+S..#....
+.#.#.##.
+.#...#..
+.####.#.
+......#E
+            ",
+        )
+    }
+
+    fn open_neighbors(grid: &Grid<char>, coord: &Coord) -> Vec<Coord> {
+        grid.neighbors_iter(*coord, false)
+            .filter(|(_, &cell)| cell != '#')
+            .map(|(coord, _)| coord)
+            .collect()
+    }
+
+    fn manhattan(a: Coord, b: Coord) -> usize {
+        a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
+    }
+
+    #[test]
+    fn test_bfs() {
+        let maze = create_maze();
+
+        let (steps, path) = bfs(
+            (0, 0),
+            |coord| open_neighbors(&maze, coord),
+            |&coord| coord == (7, 4),
+        )
+        .unwrap();
+
+        assert_eq!(steps, 15);
+        assert_eq!(path.len(), 16);
+        assert_eq!(path[0], (0, 0));
+        assert_eq!(path[15], (7, 4));
+        assert!(path.windows(2).all(|pair| manhattan(pair[0], pair[1]) == 1));
+    }
+
+    #[test]
+    fn test_bfs_unreachable() {
+        let maze = create_maze();
+
+        assert_eq!(
+            bfs(
+                (0, 0),
+                |coord| open_neighbors(&maze, coord),
+                |&coord| coord == (3, 0),
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_bfs_multi() {
+        let maze = create_maze();
+
+        let (steps, path) = bfs_multi(
+            [(0, 0), (4, 0)],
+            |coord| open_neighbors(&maze, coord),
+            |&coord| coord == (7, 4),
+        )
+        .unwrap();
+
+        assert_eq!(steps, 7);
+        assert_eq!(path[0], (4, 0));
+    }
+
+    #[test]
+    fn test_bfs_all() {
+        let maze = create_maze();
+
+        let distances = bfs_all((0, 0), |coord| open_neighbors(&maze, coord));
+
+        assert_eq!(distances[&(0, 0)], 0);
+        assert_eq!(distances[&(2, 2)], 4);
+        assert_eq!(distances[&(7, 4)], 15);
+        assert_eq!(distances.get(&(1, 1)), None);
+        assert_eq!(
+            distances.len(),
+            maze.iter().filter(|(_, &cell)| cell != '#').count()
+        );
+    }
+
+    fn weighted_successors(state: &u32) -> Vec<(u32, u32)> {
+        // 0 -> 1 (1), 0 -> 2 (4), 1 -> 2 (1), 1 -> 3 (5), 2 -> 3 (1)
+        match state {
+            0 => vec![(1, 1), (2, 4)],
+            1 => vec![(2, 1), (3, 5)],
+            2 => vec![(3, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        assert_eq!(
+            dijkstra(0, weighted_successors, |&state| state == 3),
+            Some((3, vec![0, 1, 2, 3]))
+        );
+        assert_eq!(dijkstra(3, weighted_successors, |&state| state == 0), None);
+    }
+
+    #[test]
+    fn test_dijkstra_multi() {
+        assert_eq!(
+            dijkstra_multi([0, 2], weighted_successors, |&state| state == 3),
+            Some((1, vec![2, 3]))
+        );
+    }
+
+    #[test]
+    fn test_dijkstra_all() {
+        let costs = dijkstra_all(0, weighted_successors);
+
+        assert_eq!(costs, HashMap::from([(0, 0), (1, 1), (2, 2), (3, 3)]));
+    }
+
+    #[test]
+    fn test_astar() {
+        let maze = create_maze();
+        let goal = (7, 4);
+
+        let (cost, path) = astar(
+            (0, 0),
+            |coord| {
+                open_neighbors(&maze, coord)
+                    .into_iter()
+                    .map(|coord| (coord, 1))
+            },
+            |&coord| manhattan(coord, goal),
+            |&coord| coord == goal,
+        )
+        .unwrap();
+
+        assert_eq!(cost, 15);
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&goal));
+    }
+}
@@ -0,0 +1,235 @@
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{BitGrid, Grid, Topology};
+
+#[derive(Serialize, Deserialize)]
+struct GridData<R> {
+    #[serde(default)]
+    topology: Topology,
+    rows: Vec<R>,
+}
+
+/// Serialized as `{topology, rows}`, each row an array of cells. A missing topology reads
+/// as `Bounded`.
+///
+/// Without specialization this impl also covers `Grid<char>`, so row strings need
+/// `CharGrid` or the `char_rows` adapter.
+impl<T: Serialize> Serialize for Grid<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        GridData {
+            topology: self.topology(),
+            rows: self.rows().collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Grid<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = GridData::<Vec<T>>::deserialize(deserializer)?;
+
+        from_rows(data.rows, data.topology).map_err(D::Error::custom)
+    }
+}
+
+/// `Grid<char>` with each row as a string, for `#[serde(with = "helpers::serialize::char_rows")]`.
+pub mod char_rows {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(grid: &Grid<char>, serializer: S) -> Result<S::Ok, S::Error> {
+        GridData {
+            topology: grid.topology(),
+            rows: grid
+                .rows()
+                .map(|row| row.iter().collect::<String>())
+                .collect(),
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Grid<char>, D::Error> {
+        let data = GridData::<String>::deserialize(deserializer)?;
+        let rows = data.rows.iter().map(|row| row.chars().collect()).collect();
+
+        from_rows(rows, data.topology).map_err(D::Error::custom)
+    }
+}
+
+/// `Grid<char>` serialized as `{topology, rows}` with each row as a string.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CharGrid(#[serde(with = "char_rows")] pub Grid<char>);
+
+impl From<Grid<char>> for CharGrid {
+    fn from(grid: Grid<char>) -> Self {
+        CharGrid(grid)
+    }
+}
+
+impl From<CharGrid> for Grid<char> {
+    fn from(grid: CharGrid) -> Self {
+        grid.0
+    }
+}
+
+/// Serialized as row strings drawn with `#` and `.`.
+impl Serialize for BitGrid {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq((0..self.size.1).map(|y| {
+            (0..self.size.0)
+                .map(|x| if self.get((x, y)) { '#' } else { '.' })
+                .collect::<String>()
+        }))
+    }
+}
+
+impl<'de> Deserialize<'de> for BitGrid {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let rows = Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .enumerate()
+            .map(|(y, row)| {
+                row.chars()
+                    .enumerate()
+                    .map(|(x, c)| match c {
+                        '#' => Ok(true),
+                        '.' => Ok(false),
+                        _ => Err(D::Error::custom(format!(
+                            "Unexpected {:?} at ({}, {}), expected '#' or '.'",
+                            c, x, y
+                        ))),
+                    })
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
+        let grid: Grid<bool> = from_rows(rows, Topology::Bounded).map_err(D::Error::custom)?;
+
+        Ok(BitGrid::from(&grid))
+    }
+}
+
+fn from_rows<T>(rows: Vec<Vec<T>>, topology: Topology) -> Result<Grid<T>, String> {
+    let width = rows.first().map_or(0, |row| row.len());
+
+    if let Some(y) = rows.iter().position(|row| row.len() != width) {
+        return Err(format!(
+            "Row {} has {} cells, expected {}",
+            y,
+            rows[y].len(),
+            width
+        ));
+    }
+
+    let height = rows.len();
+    Ok(Grid::new((width, height), rows.into_iter().flatten().collect()).with_topology(topology))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::hex::Hex;
+    use crate::{input_grid, Direction};
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Map {
+        #[serde(with = "char_rows")]
+        grid: Grid<char>,
+    }
+
+    #[test]
+    fn test_char_grid() {
+        let map = Map {
+            grid: input_grid("#..\n.#."),
+        };
+        let json = serde_json::to_string(&map).unwrap();
+
+        assert_eq!(
+            json,
+            r##"{"grid":{"topology":"Bounded","rows":["#..",".#."]}}"##
+        );
+        assert_eq!(serde_json::from_str::<Map>(&json).unwrap(), map);
+    }
+
+    #[test]
+    fn test_char_grid_newtype() {
+        let grid = CharGrid::from(input_grid("ab\ncd"));
+        let json = serde_json::to_string(&grid).unwrap();
+
+        assert_eq!(json, r#"{"topology":"Bounded","rows":["ab","cd"]}"#);
+        assert_eq!(serde_json::from_str::<CharGrid>(&json).unwrap(), grid);
+        assert_eq!(
+            serde_json::from_str::<CharGrid>(r#"{"rows":["ab","cd"]}"#).unwrap(),
+            grid
+        );
+    }
+
+    #[test]
+    fn test_grid() {
+        let grid = Grid::new((3, 2), vec![1, 2, 3, 4, 5, 6]);
+        let json = serde_json::to_string(&grid).unwrap();
+
+        assert_eq!(json, r#"{"topology":"Bounded","rows":[[1,2,3],[4,5,6]]}"#);
+        assert_eq!(serde_json::from_str::<Grid<usize>>(&json).unwrap(), grid);
+        assert_eq!(
+            serde_json::from_str::<Grid<usize>>(r#"{"rows":[[1,2,3],[4,5,6]]}"#).unwrap(),
+            grid
+        );
+
+        let empty: Grid<bool> = Grid::new((0, 0), vec![]);
+        let json = serde_json::to_string(&empty).unwrap();
+        assert_eq!(serde_json::from_str::<Grid<bool>>(&json).unwrap(), empty);
+    }
+
+    #[test]
+    fn test_grid_topology() {
+        let grid = Grid::new((2, 1), vec!['a', 'b']).with_topology(Topology::Torus);
+
+        let json = serde_json::to_string(&grid).unwrap();
+        let read: Grid<char> = serde_json::from_str(&json).unwrap();
+        assert_eq!(read.topology(), Topology::Torus);
+
+        let map = Map { grid };
+        let json = serde_json::to_string(&map).unwrap();
+        let read: Map = serde_json::from_str(&json).unwrap();
+        assert_eq!(read.grid.topology(), Topology::Torus);
+    }
+
+    #[test]
+    fn test_ragged_grid() {
+        let error = serde_json::from_str::<Grid<usize>>(r#"{"rows":[[1,2],[3]]}"#).unwrap_err();
+
+        assert!(error.to_string().contains("Row 1 has 1 cells, expected 2"));
+    }
+
+    #[test]
+    fn test_bit_grid() {
+        let mut grid = BitGrid::new((3, 2));
+        grid.set((1, 0), true);
+        grid.set((2, 1), true);
+        let json = serde_json::to_string(&grid).unwrap();
+
+        assert_eq!(json, r##"[".#.","..#"]"##);
+        assert_eq!(serde_json::from_str::<BitGrid>(&json).unwrap(), grid);
+    }
+
+    #[test]
+    fn test_bit_grid_unexpected_character() {
+        let error = serde_json::from_str::<BitGrid>(r##"[".#.",".O#"]"##).unwrap_err();
+
+        assert!(error.to_string().contains("Unexpected 'O' at (1, 1)"));
+    }
+
+    #[test]
+    fn test_points() {
+        let hex = Hex::new(2, -1);
+        let json = serde_json::to_string(&(hex, Direction::TopLeft, Topology::Torus)).unwrap();
+
+        assert_eq!(json, r#"[{"q":2,"r":-1},"TopLeft","Torus"]"#);
+        assert_eq!(
+            serde_json::from_str::<(Hex, Direction, Topology)>(&json).unwrap(),
+            (hex, Direction::TopLeft, Topology::Torus)
+        );
+    }
+}
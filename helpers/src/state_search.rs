@@ -0,0 +1,331 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+/// Search problem whose score is maximised.
+pub trait State: Clone {
+    type Score: Copy + Ord;
+    /// States with the same key must have the same future, so only the best-scoring one
+    /// is expanded.
+    type Key: Eq + Hash;
+
+    fn successors(&self) -> Vec<Self>;
+
+    fn score(&self) -> Self::Score;
+
+    /// Best score reachable from this state, never less than the true one.
+    fn upper_bound(&self) -> Self::Score;
+
+    fn key(&self) -> Self::Key;
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct SearchStats {
+    /// States whose successors were generated.
+    pub expanded: usize,
+    pub generated: usize,
+    /// States discarded because their bound could not beat the best score.
+    pub pruned: usize,
+    /// States discarded because a state with the same key scored at least as well.
+    pub duplicates: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Search<S> {
+    pub best: S,
+    pub stats: SearchStats,
+}
+
+/// Depth-first branch and bound, exact as long as `upper_bound` is optimistic.
+pub fn dfs_branch_and_bound<S: State>(initial: S) -> Search<S> {
+    let mut search = Searcher::new(&initial);
+    let bound = initial.upper_bound();
+    let mut stack = vec![(initial, bound)];
+
+    while let Some((state, bound)) = stack.pop() {
+        // The best score may have improved since the state was pushed
+        if bound <= search.best_score {
+            search.stats.pruned += 1;
+            continue;
+        }
+
+        let mut successors = search.expand(&state);
+        // Most promising last, so it is explored first
+        successors.sort_by_key(|(_, bound)| *bound);
+        stack.extend(successors);
+    }
+
+    search.finish()
+}
+
+/// Keeps only the `width` most promising states of each depth, by upper bound. Fast but
+/// may miss the best state.
+pub fn beam<S: State>(initial: S, width: usize) -> Search<S> {
+    assert!(width > 0, "Beam width must be positive");
+
+    let mut search = Searcher::new(&initial);
+    let mut layer = vec![initial];
+
+    while !layer.is_empty() {
+        let mut next: Vec<(S, S::Score)> = layer
+            .iter()
+            .flat_map(|state| search.expand(state))
+            .collect();
+
+        next.sort_by_key(|(_, bound)| Reverse(*bound));
+        if next.len() > width {
+            search.stats.pruned += next.len() - width;
+            next.truncate(width);
+        }
+
+        layer = next.into_iter().map(|(state, _)| state).collect();
+    }
+
+    search.finish()
+}
+
+/// Expands states by decreasing upper bound and stops as soon as none can beat the best
+/// score, exact as long as `upper_bound` is optimistic.
+pub fn best_first<S: State>(initial: S) -> Search<S> {
+    let mut search = Searcher::new(&initial);
+    let mut heap = BinaryHeap::new();
+    let bound = initial.upper_bound();
+    heap.push(Candidate {
+        state: initial,
+        bound,
+    });
+
+    while let Some(Candidate { state, bound }) = heap.pop() {
+        if bound <= search.best_score {
+            search.stats.pruned += heap.len() + 1;
+            break;
+        }
+
+        for (state, bound) in search.expand(&state) {
+            heap.push(Candidate { state, bound });
+        }
+    }
+
+    search.finish()
+}
+
+/// Best state and bookkeeping shared by the strategies.
+struct Searcher<S: State> {
+    best: S,
+    best_score: S::Score,
+    seen: HashMap<S::Key, S::Score>,
+    stats: SearchStats,
+}
+
+impl<S: State> Searcher<S> {
+    fn new(initial: &S) -> Self {
+        Searcher {
+            best: initial.clone(),
+            best_score: initial.score(),
+            seen: HashMap::from([(initial.key(), initial.score())]),
+            stats: SearchStats::default(),
+        }
+    }
+
+    /// Successors worth exploring, with their upper bounds.
+    fn expand(&mut self, state: &S) -> Vec<(S, S::Score)> {
+        self.stats.expanded += 1;
+        let mut kept = Vec::new();
+
+        for next in state.successors() {
+            self.stats.generated += 1;
+            let score = next.score();
+
+            match self.seen.entry(next.key()) {
+                Entry::Occupied(entry) if *entry.get() >= score => {
+                    self.stats.duplicates += 1;
+                    continue;
+                }
+                Entry::Occupied(mut entry) => {
+                    entry.insert(score);
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(score);
+                }
+            }
+
+            if score > self.best_score {
+                self.best = next.clone();
+                self.best_score = score;
+            }
+
+            let bound = next.upper_bound();
+            if bound > self.best_score {
+                kept.push((next, bound));
+            } else {
+                self.stats.pruned += 1;
+            }
+        }
+
+        kept
+    }
+
+    fn finish(self) -> Search<S> {
+        Search {
+            best: self.best,
+            stats: self.stats,
+        }
+    }
+}
+
+struct Candidate<S: State> {
+    state: S,
+    bound: S::Score,
+}
+
+impl<S: State> PartialEq for Candidate<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bound == other.bound
+    }
+}
+
+impl<S: State> Eq for Candidate<S> {}
+
+impl<S: State> PartialOrd for Candidate<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S: State> Ord for Candidate<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.bound.cmp(&other.bound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // (weight, value)
+    const ITEMS: [(usize, usize); 8] = [
+        (12, 4),
+        (2, 2),
+        (2, 2),
+        (4, 10),
+        (1, 2),
+        (7, 9),
+        (5, 6),
+        (3, 5),
+    ];
+    const CAPACITY: usize = 15;
+
+    #[derive(Debug, Clone)]
+    struct Knapsack {
+        index: usize,
+        weight: usize,
+        value: usize,
+    }
+
+    impl State for Knapsack {
+        type Score = usize;
+        type Key = (usize, usize);
+
+        fn successors(&self) -> Vec<Self> {
+            let Some(&(weight, value)) = ITEMS.get(self.index) else {
+                return vec![];
+            };
+
+            let mut successors = vec![Knapsack {
+                index: self.index + 1,
+                ..self.clone()
+            }];
+
+            if self.weight + weight <= CAPACITY {
+                successors.push(Knapsack {
+                    index: self.index + 1,
+                    weight: self.weight + weight,
+                    value: self.value + value,
+                });
+            }
+
+            successors
+        }
+
+        fn score(&self) -> usize {
+            self.value
+        }
+
+        fn upper_bound(&self) -> usize {
+            self.value
+                + ITEMS[self.index..]
+                    .iter()
+                    .filter(|(weight, _)| self.weight + weight <= CAPACITY)
+                    .map(|(_, value)| value)
+                    .sum::<usize>()
+        }
+
+        fn key(&self) -> (usize, usize) {
+            (self.index, self.weight)
+        }
+    }
+
+    fn initial() -> Knapsack {
+        Knapsack {
+            index: 0,
+            weight: 0,
+            value: 0,
+        }
+    }
+
+    fn brute_force() -> usize {
+        (0..1 << ITEMS.len())
+            .map(|mask: usize| {
+                let taken = ITEMS
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| mask & (1 << index) != 0);
+
+                taken.fold((0, 0), |(weight, value), (_, item)| {
+                    (weight + item.0, value + item.1)
+                })
+            })
+            .filter(|&(weight, _)| weight <= CAPACITY)
+            .map(|(_, value)| value)
+            .max()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_dfs_branch_and_bound() {
+        let search = dfs_branch_and_bound(initial());
+
+        assert_eq!(search.best.value, brute_force());
+        assert!(search.best.weight <= CAPACITY);
+        assert!(search.stats.pruned > 0);
+        assert!(search.stats.generated < 2 << ITEMS.len());
+    }
+
+    #[test]
+    fn test_best_first() {
+        let search = best_first(initial());
+
+        assert_eq!(search.best.value, brute_force());
+        assert!(search.best.weight <= CAPACITY);
+        assert!(search.stats.pruned > 0);
+    }
+
+    #[test]
+    fn test_beam() {
+        assert_eq!(beam(initial(), 1000).best.value, brute_force());
+
+        let narrow = beam(initial(), 1);
+        assert!(narrow.best.value <= brute_force());
+        assert!(narrow.best.weight <= CAPACITY);
+        assert!(narrow.stats.expanded <= ITEMS.len() + 1);
+    }
+
+    #[test]
+    fn test_duplicates() {
+        let search = beam(initial(), 1000);
+
+        // Taking either of the two identical items reaches the same key
+        assert!(search.stats.duplicates > 0);
+    }
+}